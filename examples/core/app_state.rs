@@ -0,0 +1,57 @@
+use avocado::prelude::*;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum GameState {
+    Loading,
+    Playing,
+    GameOver,
+}
+
+#[derive(Component)]
+struct Player;
+
+fn main() {
+    App::new()
+        .init::<LogSubsystem>()
+        .init::<CoreSubsystem>()
+
+        .state(GameState::Loading)
+        .enter_sys(GameState::Loading, load)
+        .transition_sys(GameState::Loading, GameState::Playing, loaded)
+        .enter_sys(GameState::Playing, spawn_player)
+        .exit_sys(GameState::Playing, leave)
+        .enter_sys(GameState::GameOver, exit)
+
+        .sys(CoreStage::Update, play.run_in_state(GameState::Playing))
+        .run();
+}
+
+fn load(mut commands: Commands) {
+    log::info!("Loading...");
+    commands.insert_resource(NextState(GameState::Playing));
+}
+
+fn loaded() {
+    log::info!("Done loading");
+}
+
+fn spawn_player(mut commands: Commands) {
+    commands.spawn((Player, StateScoped(GameState::Playing)));
+}
+
+fn play(mut commands: Commands, players: Query<(), With<Player>>, mut frames: Local<u8>) {
+    *frames += 1;
+    log::info!("Playing with {} player(s)", players.iter().count());
+
+    if *frames >= 5 {
+        commands.insert_resource(NextState(GameState::GameOver));
+    }
+}
+
+fn leave() {
+    log::info!("Game over; players will be despawned");
+}
+
+fn exit(mut exit: EventWriter<ExitEvent>) {
+    exit.send(ExitEvent::graceful());
+}
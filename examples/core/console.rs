@@ -0,0 +1,28 @@
+use avocado::prelude::*;
+
+#[derive(Resource, Default)]
+struct Score(i64);
+
+fn main() {
+    App::new()
+        .init::<LogSubsystem>()
+        .init::<CoreSubsystem>()
+        .init::<ConsoleSubsystem>()
+
+        .init_res::<Score>()
+        .cvar::<Score, i64>("score", "The current score", |score| score.0, |score, value| score.0 = value)
+        .console_command(CommandDesc::new("add")
+            .help("Adds to the score")
+            .arg("amount", ArgKind::Int),
+            add,
+        )
+
+        .run();
+}
+
+fn add(In(args): In<CommandArgs>, mut score: ResMut<Score>, mut console: ResMut<Console>) -> CommandResult {
+    score.0 += args.int("amount").ok_or("Missing amount")?;
+    console.print(format!("Score is now {}", score.0));
+
+    Ok(())
+}
@@ -8,22 +8,13 @@ struct AtlasHandle(Option<Handle<TextureAtlas>>);
 #[derive(SystemLabel)]
 struct SpriteBatchLabel;
 
-#[derive(StageLabel)]
-struct Poll;
-
-#[derive(Resource, Deref, DerefMut)]
-struct PollUpdate(FixedUpdate);
-impl FixedUpdateWrap for PollUpdate {
-    fn new(_: &mut World, updater: FixedUpdate) -> Self {
-        Self(updater)
-    }
-}
-
-#[derive(Resource, Default, Deref, DerefMut)]
-struct FPS(usize);
-
 fn main() {
     App::new()
+        .insert_res(DiagnosticConfig {
+            log_interval: Some(1.),
+            ..default()
+        })
+
         .init::<AVocado>()
         .init_res::<AtlasHandle>()
 
@@ -39,11 +30,6 @@ fn main() {
             builder.build()
         })
 
-        .init_res::<FPS>()
-        .fixed_timestep_sec::<PollUpdate>(CoreStage::Update, Poll, SystemStage::parallel(), 1.0)
-        .sys(CoreStage::Update, incr)
-        .sys(Poll, poll.run_if(FixedUpdate::qualified_sys::<PollUpdate>))
-
         .sys(CoreStage::Update, check)
         .sys(CoreStage::Update, behave)
         .sys(RenderStage::Begin, resize
@@ -89,15 +75,6 @@ fn load_atlas(
     Ok(vec![handle.as_dyn()])
 }
 
-fn incr(mut frame: ResMut<FPS>) {
-    **frame += 1;
-}
-
-fn poll(mut frame: ResMut<FPS>) {
-    log::info!("FPS: {}", **frame);
-    **frame = 0;
-}
-
 fn check(
     events: EventReader<AssetGraphDoneEvent>,
     mut commands: Commands,
@@ -112,27 +89,32 @@ fn check(
         let atlas = atlases.remove(handle).unwrap();
 
         let PhysicalSize { width, height, } = surface.size.cast::<f32>();
-        global_camera.entity = commands.spawn(Camera {
-            position: Vec3 { x: -0., y: -0., z: 10., },
-            near: -5.,
-            far: 5.,
-            proj: CameraProj::Orthographic { width, height, },
-        }).id();
-
-        commands.spawn(SpriteHolder {
-            sprites: vec![Sprite::<DefSpriteVertex> {
-                region: atlas.region(Path::new("ball.png")),
-                color: Color::rgb(1., 1., 1.),
-                desc: SpriteDesc::Transform {
-                    pos: Vec2::splat(-64.),
-                    z: 0.,
-                    anchor: Vec2::splat(64.),
-                    size: Vec2::splat(128.),
-                    rotation: 0.,
-                    data: (),
-                },
-            }]
-        });
+        global_camera.entity = commands.spawn((
+            Camera {
+                near: -5.,
+                far: 5.,
+                proj: CameraProj::Orthographic { width, height, },
+            },
+            TransformBundle::default(),
+        )).id();
+
+        commands.spawn((
+            SpriteHolder {
+                sprites: vec![Sprite::<DefSpriteVertex> {
+                    region: atlas.region(Path::new("ball.png")),
+                    color: Color::rgb(1., 1., 1.),
+                    desc: SpriteDesc::Transform {
+                        offset: Vec2::splat(-64.),
+                        z: 0.,
+                        anchor: Vec2::splat(64.),
+                        size: Vec2::splat(128.),
+                        rotation: 0.,
+                        data: (),
+                    },
+                }]
+            },
+            TransformBundle::default(),
+        ));
 
         commands.insert_resource(atlas);
 
@@ -140,24 +122,14 @@ fn check(
     }
 }
 
-fn behave(time: Res<Time>, mut holders: Query<&mut SpriteHolder<DefSpriteVertex>>) {
+fn behave(time: Res<Time>, mut holders: Query<&mut Transform, With<SpriteHolder<DefSpriteVertex>>>) {
     let (sin, cos) = (time.elapsed_sec() as f32).sin_cos();
-    let new_pos = Vec2 { x: cos * 256. - 64., y: sin * 256. - 64. };
+    let new_pos = Vec2 { x: cos * 256., y: sin * 256. };
     let delta = time.delta_sec() as f32 * 60.;
 
-    for mut holder in &mut holders {
-        for sprite in &mut holder.sprites {
-            let SpriteDesc::Transform {
-                pos,
-                rotation,
-                ..
-            } = &mut sprite.desc else {
-                continue;
-            };
-
-            *pos = new_pos;
-            *rotation += delta;
-        }
+    for mut transform in &mut holders {
+        transform.translation = new_pos;
+        transform.rotation += delta;
     }
 }
 
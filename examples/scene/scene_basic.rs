@@ -0,0 +1,51 @@
+use avocado::prelude::*;
+use std::path::Path;
+
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+struct Health {
+    current: u32,
+    max: u32,
+}
+
+fn main() {
+    App::new()
+        .init::<LogSubsystem>()
+        .init::<CoreSubsystem>()
+        .init::<TransformSubsystem>()
+        .init::<AssetSubsystem>()
+        .init::<SceneSubsystem>()
+
+        .register_type::<Health>()
+
+        .startup_sys(startup)
+        .sys(CoreStage::Update, spawned)
+
+        .run();
+}
+
+fn startup(mut server: ResMut<AssetServer>, mut spawner: ResMut<SceneSpawner>) {
+    spawner.spawn(server.load::<Scene>(Path::new("scene/level.scn.ron")));
+}
+
+fn spawned(world: &mut World) {
+    let events = world
+        .resource_mut::<Events<SceneSpawnedEvent>>()
+        .drain()
+        .collect::<Vec<_>>();
+
+    for event in events {
+        log::info!("Spawned {} entities from {:?}", event.entities.len(), event.scene.path());
+
+        // Write the spawned entities back out; entity identifiers will differ, but references stay consistent.
+        let scene = Scene::from_world(world, event.entities);
+        let registry = world.resource::<AppTypeRegistry>().clone();
+
+        match scene.serialize_ron(&registry.read()) {
+            Ok(ron) => log::info!("{}", ron),
+            Err(err) => log::error!("{}", err),
+        }
+
+        world.send_event(ExitEvent::graceful());
+    }
+}
@@ -84,6 +84,17 @@ impl<T: Asset> Assets<T> {
         }
     }
 
+    /// Number of loaded assets.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
     #[inline]
     pub(crate) fn new(ref_change: Sender<RefChange>) -> Self {
         Self {
@@ -15,7 +15,27 @@ impl AppExt for App {
         let assets = self.res_mut::<AssetServer>().unwrap().register::<T>();
         self
             .insert_res(assets)
-            .sys(CoreStage::SysUpdate, AssetServer::update_sys::<T>)
+            .sys(CoreStage::SysUpdate, AssetServer::update_sys::<T>);
+
+        #[cfg(feature = "diagnostic")]
+        {
+            use crate::{
+                asset::Assets,
+                diagnostic::{
+                    AppExt as _,
+                    Diagnostics,
+                },
+            };
+
+            let name = format!("asset.{}", bevy_utils::get_short_name(std::any::type_name::<T>()));
+            self
+                .diagnostic(name.clone(), "")
+                .sys(CoreStage::SysPostUpdate, move |assets: Res<Assets<T>>, mut diagnostics: ResMut<Diagnostics>| {
+                    diagnostics.add(&name, assets.len() as f64);
+                });
+        }
+
+        self
     }
 
     fn asset_loader<T: Asset>(&mut self, loader: impl AssetLoader) -> &mut Self {
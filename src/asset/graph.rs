@@ -19,11 +19,13 @@ use std::{
 pub type AssetGraphIn = HashMap<&'static str, AssetGraphOut>;
 pub type AssetGraphOut = Vec<HandleDyn>;
 pub type AssetGraphResult = Result<AssetGraphOut, anyhow::Error>;
+pub type AssetGraphDone = Box<dyn FnOnce(&mut World) + Send + Sync>;
 
 #[derive(Resource)]
 pub struct AssetGraph {
     nodes: Vec<AssetGraphNode>,
     data: AssetGraphIn,
+    on_done: Option<AssetGraphDone>,
 
     ran: bool,
     to_update: Vec<(usize, AssetGraphOut)>,
@@ -51,16 +53,20 @@ impl AssetGraph {
                 world.remove_resource::<Self>();
                 world.send_event(ExitEvent::error(msg.clone()));
             } else if *done.read() {
-                world.remove_resource::<Self>();
+                if let Some(on_done) = world.remove_resource::<Self>().and_then(|graph| graph.on_done) {
+                    on_done(world);
+                }
+
                 world.send_event(AssetGraphDoneEvent);
             }; // This semicolon is necessary.
         }
     }
 
-    fn new(nodes: Vec<AssetGraphNode>) -> Self {
+    fn new(nodes: Vec<AssetGraphNode>, on_done: Option<AssetGraphDone>) -> Self {
         Self {
             nodes,
             data: AssetGraphIn::default(),
+            on_done,
 
             ran: false,
             to_update: vec![],
@@ -164,11 +170,22 @@ pub struct AssetGraphNode {
 pub struct AssetGraphBuilder {
     nodes: Vec<AssetGraphNode>,
     labels: HashMap<&'static str, usize>,
+    on_done: Option<AssetGraphDone>,
 }
 
 impl AssetGraphBuilder {
     pub fn build(self) -> AssetGraph {
-        AssetGraph::new(self.nodes)
+        AssetGraph::new(self.nodes, self.on_done)
+    }
+
+    /// Runs `on_done` once every node in the graph has finished loading.
+    pub fn on_done(&mut self, on_done: impl FnOnce(&mut World) + Send + Sync + 'static) {
+        self.on_done = Some(Box::new(on_done));
+    }
+
+    /// Transitions to `state` once every node in the graph has finished loading.
+    pub fn done_state<S: AppState>(&mut self, state: S) {
+        self.on_done(move |world| world.insert_resource(NextState(state)));
     }
 
     pub fn node<Param>(
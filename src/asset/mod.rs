@@ -1,5 +1,7 @@
 use crate::core::prelude::*;
 use std::sync::Arc;
+#[cfg(feature = "asset_folder")]
+use std::path::PathBuf;
 
 mod asset;
 mod event;
@@ -26,7 +28,7 @@ pub mod prelude {
         Asset, AssetDyn, Assets, AssetServer, AssetLoader, AssetReader,
         Handle, HandleDyn, AssetState,
         AssetData, NoAssetData, AssetLoadSync,
-        AssetGraph, AssetGraphIn, AssetGraphOut, AssetGraphResult, AssetGraphBuilder, AssetLoaderError,
+        AssetGraph, AssetGraphIn, AssetGraphOut, AssetGraphResult, AssetGraphDone, AssetGraphBuilder, AssetLoaderError,
         AssetGraphDoneEvent,
     };
 }
@@ -34,11 +36,21 @@ pub mod prelude {
 pub struct AssetSubsystem;
 impl Subsystem for AssetSubsystem {
     fn init(app: &mut App) {
+        let reader: Arc<dyn AssetReader> = match app.args().value("asset-folder") {
+            #[cfg(feature = "asset_folder")]
+            Some(folder) => Arc::new(AssetFolderReader::new(PathBuf::from(folder))),
+            _ => Arc::new(DefaultAssetReader::default()),
+        };
+
         app
             .event::<AssetGraphDoneEvent>()
-            .insert_res(AssetServer::new(Arc::new(DefaultAssetReader::default())))
+            .insert_res(AssetServer::new(reader))
 
             .sys(CoreStage::SysPostUpdate, AssetServer::post_update_sys.at_end())
             .sys(CoreStage::SysPostUpdate, AssetGraph::update_sys.at_end());
     }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![SubsystemId::of::<CoreSubsystem>()]
+    }
 }
@@ -67,7 +67,7 @@ mod folder {
         }
 
         fn read_file(&self, path: &Path) -> Result<Vec<u8>, io::Error> {
-            self.read_relative(path, path)
+            self.read_relative(Path::new(""), path)
         }
 
         fn read_relative(&self, path: &Path, rel: &Path) -> Result<Vec<u8>, io::Error> {
@@ -0,0 +1,246 @@
+use bevy_ecs::{
+    prelude::*,
+    system::BoxedSystem,
+};
+use bevy_utils::HashMap;
+use std::{
+    fmt,
+    mem,
+};
+
+pub type CommandResult = Result<(), String>;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ArgKind {
+    Int,
+    Float,
+    Bool,
+    Str,
+    /// Every remaining token, joined by spaces. Must be the last argument.
+    Rest,
+}
+
+impl fmt::Display for ArgKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::Bool => "bool",
+            Self::Str => "string",
+            Self::Rest => "text",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandArgDesc {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    pub optional: bool,
+}
+
+/// Name, help, and argument schema of a console command.
+#[derive(Debug, Clone)]
+pub struct CommandDesc {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub args: Vec<CommandArgDesc>,
+}
+
+impl CommandDesc {
+    #[inline]
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            help: "",
+            args: vec![],
+        }
+    }
+
+    #[inline]
+    pub fn help(self, help: &'static str) -> Self {
+        Self { help, ..self }
+    }
+
+    /// Adds a required argument. Panics if it follows an optional or [`ArgKind::Rest`] argument.
+    pub fn arg(mut self, name: &'static str, kind: ArgKind) -> Self {
+        if self.args.last().map_or(false, |last| last.optional || last.kind == ArgKind::Rest) {
+            panic!("Required argument {} of command {} follows an optional one", name, self.name);
+        }
+
+        self.args.push(CommandArgDesc { name, kind, optional: false, });
+        self
+    }
+
+    /// Adds an optional argument. Panics if it follows an [`ArgKind::Rest`] argument.
+    pub fn opt_arg(mut self, name: &'static str, kind: ArgKind) -> Self {
+        if self.args.last().map_or(false, |last| last.kind == ArgKind::Rest) {
+            panic!("Argument {} of command {} follows a rest argument", name, self.name);
+        }
+
+        self.args.push(CommandArgDesc { name, kind, optional: true, });
+        self
+    }
+
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.to_string();
+        for arg in &self.args {
+            usage.push_str(&if arg.optional {
+                format!(" [{}: {}]", arg.name, arg.kind)
+            } else {
+                format!(" <{}: {}>", arg.name, arg.kind)
+            });
+        }
+
+        usage
+    }
+
+    /// Parses `tokens` against the argument schema.
+    pub fn parse(&self, tokens: &[String]) -> Result<CommandArgs, String> {
+        let mut values = HashMap::default();
+        let mut tokens = tokens.iter();
+
+        for arg in &self.args {
+            let value = match arg.kind {
+                ArgKind::Rest => {
+                    let rest = tokens.by_ref().map(String::as_str).collect::<Vec<_>>().join(" ");
+                    if rest.is_empty() { None } else { Some(ArgValue::Str(rest)) }
+                },
+                kind => match tokens.next() {
+                    Some(token) => Some(match kind {
+                        ArgKind::Int => ArgValue::Int(token.parse().map_err(|_| format!("`{}` isn't an int", token))?),
+                        ArgKind::Float => ArgValue::Float(token.parse().map_err(|_| format!("`{}` isn't a float", token))?),
+                        ArgKind::Bool => ArgValue::Bool(parse_bool(token).ok_or_else(|| format!("`{}` isn't a bool", token))?),
+                        _ => ArgValue::Str(token.clone()),
+                    }),
+                    None => None,
+                },
+            };
+
+            match value {
+                Some(value) => { values.insert(arg.name, value); },
+                None if arg.optional => {},
+                None => return Err(format!("Missing argument {}; usage: {}", arg.name, self.usage())),
+            }
+        }
+
+        if tokens.next().is_some() {
+            return Err(format!("Too many arguments; usage: {}", self.usage()));
+        }
+
+        Ok(CommandArgs { values })
+    }
+}
+
+/// Arguments passed to a command handler system, parsed according to its [`CommandDesc`].
+#[derive(Debug, Clone, Default)]
+pub struct CommandArgs {
+    values: HashMap<&'static str, ArgValue>,
+}
+
+impl CommandArgs {
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&ArgValue> {
+        self.values.get(name)
+    }
+
+    #[inline]
+    pub fn int(&self, name: &str) -> Option<i64> {
+        match self.get(name)? {
+            ArgValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn float(&self, name: &str) -> Option<f64> {
+        match self.get(name)? {
+            ArgValue::Float(value) => Some(*value),
+            ArgValue::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn bool(&self, name: &str) -> Option<bool> {
+        match self.get(name)? {
+            ArgValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn str(&self, name: &str) -> Option<&str> {
+        match self.get(name)? {
+            ArgValue::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) struct ConsoleCommand {
+    pub desc: CommandDesc,
+    pub system: BoxedSystem<CommandArgs, CommandResult>,
+    pub initialized: bool,
+}
+
+impl ConsoleCommand {
+    pub fn run(&mut self, args: CommandArgs, world: &mut World) -> CommandResult {
+        if !self.initialized {
+            self.system.initialize(world);
+            self.initialized = true;
+        }
+
+        let result = self.system.run(args, world);
+        self.system.apply_buffers(world);
+        result
+    }
+}
+
+/// Accepts `true`/`false`, `on`/`off`, `yes`/`no` and `1`/`0`.
+pub fn parse_bool(token: &str) -> Option<bool> {
+    match token.to_lowercase().as_str() {
+        "true" | "on" | "yes" | "1" => Some(true),
+        "false" | "off" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Splits a line by whitespace, keeping double-quoted parts together.
+pub fn tokenize(line: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut quoted = false;
+    let mut pending = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                pending = true;
+            },
+            c if c.is_whitespace() && !quoted => if pending {
+                tokens.push(mem::take(&mut token));
+                pending = false;
+            },
+            c => {
+                token.push(c);
+                pending = true;
+            },
+        }
+    }
+
+    if pending {
+        tokens.push(token);
+    }
+
+    tokens
+}
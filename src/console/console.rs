@@ -0,0 +1,272 @@
+use crate::console::{
+    CommandArgs, CommandDesc, CommandResult, ConsoleCommand,
+    tokenize,
+};
+use bevy_ecs::prelude::*;
+use std::{
+    collections::{
+        BTreeMap, VecDeque,
+    },
+    fmt::Display,
+    mem,
+    str::FromStr,
+};
+
+pub type CVarGet = Box<dyn Fn(&World) -> Option<String> + Send + Sync>;
+pub type CVarSet = Box<dyn Fn(&mut World, &str) -> Result<(), String> + Send + Sync>;
+
+/// A line printed to the console, for front ends to display.
+#[derive(Debug, Clone)]
+pub struct ConsoleOutputEvent(pub String);
+
+/// Input, output and history of the console. Submitted lines are executed at the start of
+/// [`crate::core::CoreStage::PreUpdate`].
+#[derive(Resource)]
+pub struct Console {
+    pending: Vec<String>,
+    output: Vec<String>,
+
+    history: VecDeque<String>,
+    max_history: usize,
+    lines: VecDeque<String>,
+    max_lines: usize,
+}
+
+impl Console {
+    pub fn run_sys(world: &mut World) {
+        let lines = mem::take(&mut world.resource_mut::<Self>().pending);
+        if lines.is_empty() {
+            return;
+        }
+
+        world.resource_scope(|world, mut commands: Mut<ConsoleCommands>| {
+            for line in lines {
+                commands.execute(world, &line);
+            }
+        });
+    }
+
+    pub fn flush_sys(mut console: ResMut<Self>, mut events: EventWriter<ConsoleOutputEvent>) {
+        if !console.output.is_empty() {
+            events.send_batch(mem::take(&mut console.output).into_iter().map(ConsoleOutputEvent));
+        }
+    }
+
+    /// Queues a line to be executed.
+    pub fn submit(&mut self, line: impl Into<String>) {
+        let line = line.into();
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            return;
+        }
+
+        if self.history.back().map_or(true, |last| last != trimmed) {
+            if self.history.len() >= self.max_history {
+                self.history.pop_front();
+            }
+
+            self.history.push_back(trimmed.to_string());
+        }
+
+        self.pending.push(trimmed.to_string());
+    }
+
+    pub fn print(&mut self, line: impl Into<String>) {
+        let line = line.into();
+        if self.lines.len() >= self.max_lines {
+            self.lines.pop_front();
+        }
+
+        self.lines.push_back(line.clone());
+        self.output.push(line);
+    }
+
+    /// Submitted lines, oldest first.
+    #[inline]
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &str> + ExactSizeIterator {
+        self.history.iter().map(String::as_str)
+    }
+
+    /// Recently printed lines, oldest first.
+    #[inline]
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &str> + ExactSizeIterator {
+        self.lines.iter().map(String::as_str)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self {
+            pending: vec![],
+            output: vec![],
+
+            history: VecDeque::new(),
+            max_history: 256,
+            lines: VecDeque::new(),
+            max_lines: 512,
+        }
+    }
+}
+
+/// A console variable, read by typing its name and written by typing its name followed by a value.
+pub struct CVar {
+    pub help: &'static str,
+    get: CVarGet,
+    set: CVarSet,
+}
+
+/// Registry of console commands and variables.
+#[derive(Resource, Default)]
+pub struct ConsoleCommands {
+    commands: BTreeMap<&'static str, ConsoleCommand>,
+    cvars: BTreeMap<&'static str, CVar>,
+}
+
+impl ConsoleCommands {
+    /// Names handled by the registry itself.
+    pub const BUILTINS: [&'static str; 2] = ["help", "cvars"];
+
+    /// Registers a command whose handler is a system taking its parsed arguments as input.
+    pub fn add<Params>(&mut self, desc: CommandDesc, handler: impl IntoSystem<CommandArgs, CommandResult, Params>) {
+        self.check_name(desc.name);
+        self.commands.insert(desc.name, ConsoleCommand {
+            desc,
+            system: Box::new(IntoSystem::into_system(handler)),
+            initialized: false,
+        });
+    }
+
+    pub fn cvar(
+        &mut self, name: &'static str, help: &'static str,
+        get: impl Fn(&World) -> Option<String> + Send + Sync + 'static,
+        set: impl Fn(&mut World, &str) -> Result<(), String> + Send + Sync + 'static,
+    ) {
+        self.check_name(name);
+        self.cvars.insert(name, CVar {
+            help,
+            get: Box::new(get),
+            set: Box::new(set),
+        });
+    }
+
+    /// Registers a console variable backed by a field of resource `R`.
+    pub fn res_cvar<R: Resource, T: 'static + FromStr + Display>(
+        &mut self, name: &'static str, help: &'static str,
+        get: fn(&R) -> T, set: fn(&mut R, T),
+    ) {
+        self.cvar(
+            name, help,
+            move |world| world.get_resource::<R>().map(|res| get(res).to_string()),
+            move |world, value| {
+                let value = value.parse::<T>().map_err(|_| format!("Invalid value `{}`", value))?;
+                let mut res = world.get_resource_mut::<R>().ok_or_else(|| format!("{} is unavailable", name))?;
+
+                set(&mut res, value);
+                Ok(())
+            },
+        );
+    }
+
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.commands.contains_key(name) || self.cvars.contains_key(name) || Self::BUILTINS.contains(&name)
+    }
+
+    /// Command and variable names starting with the last token of `input`, if it's the first token. Returns
+    /// `input` completed up to the longest common prefix along with every candidate.
+    pub fn complete(&self, input: &str) -> (String, Vec<&'static str>) {
+        if input.contains(char::is_whitespace) {
+            return (input.to_string(), vec![]);
+        }
+
+        let candidates = Self::BUILTINS
+            .iter()
+            .copied()
+            .chain(self.commands.keys().copied())
+            .chain(self.cvars.keys().copied())
+            .filter(|name| name.starts_with(input))
+            .collect::<Vec<_>>();
+
+        let completed = match candidates.split_first() {
+            Some((first, rest)) => rest.iter().fold(first.to_string(), |prefix, name| prefix
+                .chars()
+                .zip(name.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect()
+            ),
+            None => input.to_string(),
+        };
+
+        (completed, candidates)
+    }
+
+    pub fn execute(&mut self, world: &mut World, line: &str) {
+        let print = |world: &mut World, line: String| world.resource_mut::<Console>().print(line);
+        print(world, format!("> {}", line));
+
+        let tokens = tokenize(line);
+        let Some((name, args)) = tokens.split_first() else { return };
+
+        let result = match name.as_str() {
+            "help" => Ok(self.help(world, args.first().map(String::as_str))),
+            "cvars" => {
+                for (name, cvar) in &self.cvars {
+                    let value = (cvar.get)(world).unwrap_or_else(|| "<unavailable>".to_string());
+                    print(world, format!("{} = {}  {}", name, value, cvar.help));
+                }
+
+                Ok(())
+            },
+            name => if let Some(command) = self.commands.get_mut(name) {
+                command.desc.parse(args).and_then(|args| command.run(args, world))
+            } else if let Some(cvar) = self.cvars.get(name) {
+                if args.is_empty() {
+                    let value = (cvar.get)(world).unwrap_or_else(|| "<unavailable>".to_string());
+                    print(world, format!("{} = {}", name, value));
+                    Ok(())
+                } else {
+                    (cvar.set)(world, &args.join(" "))
+                }
+            } else {
+                Err(format!("Unknown command `{}`; type `help` for a list of commands", name))
+            },
+        };
+
+        if let Err(err) = result {
+            print(world, err);
+        }
+    }
+
+    fn help(&self, world: &mut World, name: Option<&str>) {
+        let mut console = world.resource_mut::<Console>();
+        match name {
+            Some(name) => match (self.commands.get(name), self.cvars.get(name)) {
+                (Some(command), _) => {
+                    console.print(command.desc.usage());
+                    console.print(format!("    {}", command.desc.help));
+                },
+                (_, Some(cvar)) => console.print(format!("{}  {}", name, cvar.help)),
+                _ => console.print(format!("Unknown command `{}`", name)),
+            },
+            None => {
+                console.print("help [command]  Shows help for every command, or one command");
+                console.print("cvars  Lists every console variable with its value");
+                for command in self.commands.values() {
+                    console.print(format!("{}  {}", command.desc.usage(), command.desc.help));
+                }
+            },
+        }
+    }
+
+    fn check_name(&self, name: &'static str) {
+        if self.contains(name) {
+            panic!("Console command or variable {} is already registered", name);
+        }
+    }
+}
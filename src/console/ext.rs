@@ -0,0 +1,41 @@
+use crate::{
+    core::prelude::*,
+    console::{
+        CommandArgs, CommandDesc, CommandResult,
+        ConsoleCommands,
+    },
+};
+use std::{
+    fmt::Display,
+    str::FromStr,
+};
+
+pub trait AppExt {
+    fn console_command<Params>(
+        &mut self, desc: CommandDesc,
+        handler: impl IntoSystem<CommandArgs, CommandResult, Params>,
+    ) -> &mut Self;
+
+    fn cvar<R: Resource, T: 'static + FromStr + Display>(
+        &mut self, name: &'static str, help: &'static str,
+        get: fn(&R) -> T, set: fn(&mut R, T),
+    ) -> &mut Self;
+}
+
+impl AppExt for App {
+    fn console_command<Params>(
+        &mut self, desc: CommandDesc,
+        handler: impl IntoSystem<CommandArgs, CommandResult, Params>,
+    ) -> &mut Self {
+        self.res_or(ConsoleCommands::default).add(desc, handler);
+        self
+    }
+
+    fn cvar<R: Resource, T: 'static + FromStr + Display>(
+        &mut self, name: &'static str, help: &'static str,
+        get: fn(&R) -> T, set: fn(&mut R, T),
+    ) -> &mut Self {
+        self.res_or(ConsoleCommands::default).res_cvar(name, help, get, set);
+        self
+    }
+}
@@ -0,0 +1,138 @@
+use crate::core::prelude::*;
+
+mod command;
+mod console;
+mod ext;
+mod stdin;
+
+pub use command::*;
+pub use console::*;
+pub use ext::*;
+pub use stdin::*;
+
+pub mod prelude {
+    pub use crate::console::{
+        ConsoleSubsystem, AppExt as _,
+        ConsoleConfig, Console, ConsoleCommands, ConsoleOutputEvent, StdinConsole,
+        CommandDesc, CommandArgs, CommandResult, ArgKind, ArgValue,
+    };
+}
+
+/// Configures [`ConsoleSubsystem`]. Must be inserted before the subsystem is initialized.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ConsoleConfig {
+    /// Whether to read commands from the standard input. Off by default, as anything piped into the app could run
+    /// commands; also enabled by passing `--console`.
+    pub stdin: bool,
+}
+
+pub struct ConsoleSubsystem;
+impl Subsystem for ConsoleSubsystem {
+    fn init(app: &mut App) {
+        app.arg(ArgDesc::flag("console").help("Reads console commands from the standard input"));
+
+        let config = app.res_or(ConsoleConfig::default).clone();
+        let stdin = config.stdin || app.args().flag("console");
+        app
+            .init_res::<Console>()
+            .init_res::<ConsoleCommands>()
+            .event::<ConsoleOutputEvent>()
+
+            .sys(CoreStage::PreUpdate, Console::run_sys.at_start())
+            .sys(CoreStage::SysPostUpdate, Console::flush_sys)
+
+            .console_command(CommandDesc::new("clear").help("Clears the console output"), clear_cmd)
+            .console_command(CommandDesc::new("history").help("Lists previously submitted lines"), history_cmd)
+            .console_command(CommandDesc::new("exit")
+                .help("Requests the app to exit")
+                .opt_arg("code", ArgKind::Int),
+                exit_cmd,
+            );
+
+        let mut commands = app.res_mut::<ConsoleCommands>().unwrap();
+        commands.cvar(
+            "time.paused", "Whether the game time is paused",
+            |world| world.get_resource::<Time>().map(|time| time.is_paused().to_string()),
+            |world, value| {
+                let paused = parse_bool(value).ok_or_else(|| format!("`{}` isn't a bool", value))?;
+                let mut time = world.resource_mut::<Time>();
+                if paused { time.pause() } else { time.unpause() }
+
+                Ok(())
+            },
+        );
+
+        #[cfg(feature = "log")]
+        commands.cvar(
+            "log.level", "Global log level, from `off` to `trace`",
+            |_| Some(crate::log::LogLevel::get().as_str().to_lowercase()),
+            |world, value| {
+                let level = value.parse().map_err(|_| format!("Invalid log level `{}`", value))?;
+                crate::log::LogLevel::set(level, world.get_resource_mut::<Settings>().as_deref_mut());
+                Ok(())
+            },
+        );
+
+        #[cfg(feature = "winit")]
+        commands.cvar(
+            "clear_color", "Background color, as `r g b [a]` from 0 to 1",
+            |world| world.get_resource::<crate::winit::ClearColor>().map(|color| format!("{} {} {} {}", color.r, color.g, color.b, color.a)),
+            |world, value| {
+                let channels = value
+                    .split_whitespace()
+                    .map(str::parse::<f32>)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| format!("Invalid color `{}`", value))?;
+
+                let color = match channels[..] {
+                    [r, g, b] => crate::graphics::Color::rgb(r, g, b),
+                    [r, g, b, a] => crate::graphics::Color::rgba(r, g, b, a),
+                    _ => return Err(format!("Invalid color `{}`", value)),
+                };
+
+                let mut clear = world.get_resource_mut::<crate::winit::ClearColor>().ok_or("Clear color is unavailable")?;
+                clear.0 = color;
+                Ok(())
+            },
+        );
+
+        if stdin {
+            match StdinConsole::spawn() {
+                Ok(stdin) => {
+                    app
+                        .insert_res(stdin)
+                        .sys(CoreStage::SysUpdate, StdinConsole::read_sys)
+                        .sys(CoreStage::SysPostUpdate, StdinConsole::print_sys.after(Console::flush_sys));
+                },
+                Err(err) => log::warn!("Couldn't read console input from stdin: {}", err),
+            }
+        }
+    }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![SubsystemId::of::<CoreSubsystem>()]
+    }
+}
+
+fn clear_cmd(_: In<CommandArgs>, mut console: ResMut<Console>) -> CommandResult {
+    console.clear();
+    Ok(())
+}
+
+fn history_cmd(_: In<CommandArgs>, mut console: ResMut<Console>) -> CommandResult {
+    let history = console.history().map(str::to_string).collect::<Vec<_>>();
+    for (i, line) in history.into_iter().enumerate() {
+        console.print(format!("{:4}  {}", i + 1, line));
+    }
+
+    Ok(())
+}
+
+fn exit_cmd(In(args): In<CommandArgs>, mut exit: EventWriter<ExitEvent>) -> CommandResult {
+    exit.send(match args.int("code") {
+        Some(code) => ExitEvent::code(code as i32),
+        None => ExitEvent::graceful(),
+    });
+
+    Ok(())
+}
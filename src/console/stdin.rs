@@ -0,0 +1,54 @@
+use crate::console::{
+    Console, ConsoleOutputEvent,
+};
+use bevy_ecs::prelude::*;
+use crossbeam_channel::Receiver;
+use std::{
+    io::{
+        self,
+        BufRead,
+    },
+    thread,
+};
+
+/// Reads console input from the standard input on a separate thread and prints console output to the standard
+/// output. Works without a window.
+#[derive(Resource)]
+pub struct StdinConsole {
+    receiver: Receiver<String>,
+}
+
+impl StdinConsole {
+    pub fn read_sys(stdin: Res<Self>, mut console: ResMut<Console>) {
+        while let Ok(line) = stdin.receiver.try_recv() {
+            console.submit(line);
+        }
+    }
+
+    pub fn print_sys(mut output: EventReader<ConsoleOutputEvent>) {
+        for ConsoleOutputEvent(line) in output.iter() {
+            println!("{}", line);
+        }
+    }
+
+    pub fn spawn() -> Result<Self, io::Error> {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        thread::Builder::new()
+            .name("Console stdin".to_string())
+            .spawn(move || {
+                for line in io::stdin().lock().lines() {
+                    match line {
+                        Ok(line) => if sender.send(line).is_err() {
+                            break;
+                        },
+                        Err(err) => {
+                            log::warn!("Couldn't read console input: {}", err);
+                            break;
+                        },
+                    }
+                }
+            })?;
+
+        Ok(Self { receiver })
+    }
+}
@@ -1,15 +1,26 @@
 use crate::core::{
-    StartupStage, CoreStage, CoreLabel,
-    ExitEvent, ExitReason,
+    Subsystem, SubsystemDesc, SubsystemGroup, Subsystems,
+    CrashReporter,
+    StartupStage, ShutdownStage, CoreStage, CoreLabel,
+    ExitReason, Shutdown,
     Time, FixedUpdate, FixedUpdateWrap,
+    AppState, StateStage, StateMachine,
+    TaskHandle,
+    EntityValidator,
+    AppTypeRegistry,
+    Args, ArgDesc,
+    Settings,
 };
 
 use bevy_ecs::{
     prelude::*,
     event::Event,
 };
+use bevy_reflect::GetTypeRegistration;
+use iyes_loopless::state::CurrentState;
 use parking_lot::RwLock;
 use std::{
+    any::type_name,
     mem,
     panic::{
         self,
@@ -27,9 +38,13 @@ pub struct App {
 }
 
 impl App {
+    /// Creates an app, parsing the process' command-line [`Args`] right away.
     pub fn new() -> Self {
+        let mut world = World::new();
+        world.insert_resource(Args::from_env());
+
         Self {
-            world: Some(World::new()),
+            world: Some(world),
             schedule: Some(Schedule::default()),
             runner: Some(Self::default_runner()),
         }
@@ -54,25 +69,24 @@ impl App {
                 schedule.run(world);
 
                 let exit = exit.read();
-                match &*exit {
-                    Some(ExitReason::Graceful) => break,
-                    Some(ExitReason::Error(msg)) => panic!("{}", &msg),
-                    None => {},
+                if let Some(ref reason) = *exit {
+                    break reason.clone();
                 }
             }));
 
-            if let Err(ref err) = result {
-                log::error!("App crashed: {:?}", err);
-            } else {
-                log::info!("App exited gracefully");
-            }
+            let exit_code = match result {
+                Ok(reason) => {
+                    reason.log();
+                    reason.exit_code()
+                },
+                Err(err) => {
+                    log::error!("App crashed: {}", CrashReporter::panic_message(&*err));
+                    1
+                },
+            };
 
             drop(app);
-            process::exit(if result.is_err() {
-                1
-            } else {
-                0
-            });
+            process::exit(exit_code);
         })
     }
 
@@ -92,17 +106,43 @@ impl App {
         (self.world.as_mut().unwrap(), self.schedule.as_mut().unwrap())
     }
 
-    #[inline]
+    /// Runs the app, or prints the help message and exits if `--help` was passed.
     pub fn run(&mut self) -> ! {
         let mut app = mem::replace(self, Self::empty());
+        if let Some(mut args) = app.res_mut::<Args>() {
+            if args.flag("help") {
+                println!("{}", args.help());
+                process::exit(0);
+            }
+
+            for err in args.take_errors() {
+                log::warn!("{}", err);
+            }
+
+            for arg in args.unknown() {
+                log::warn!("Unknown argument {}", arg);
+            }
+        }
 
         let runner = app.runner.take().unwrap();
         runner(app);
     }
 
+    /// Initializes a subsystem. Panics if it's already initialized, or if any of its dependencies aren't.
     #[inline]
     pub fn init<T: Subsystem>(&mut self) -> &mut Self {
-        T::init(self);
+        self.init_desc(SubsystemDesc::of::<T>())
+    }
+
+    #[inline]
+    pub fn init_group(&mut self, group: SubsystemGroup) -> &mut Self {
+        group.init(self);
+        self
+    }
+
+    pub(crate) fn init_desc(&mut self, desc: SubsystemDesc) -> &mut Self {
+        self.res_or(Subsystems::default).register(&desc);
+        (desc.init)(self);
         self
     }
 
@@ -212,6 +252,67 @@ impl App {
         self
     }
 
+    /// Polls every [`TaskHandle<T>`] component each frame, replacing them with [`crate::core::TaskOutput<T>`] once
+    /// finished.
+    #[inline]
+    pub fn task<T: 'static + Send + Sync>(&mut self) -> &mut Self {
+        self.sys(CoreStage::PreUpdate, TaskHandle::<T>::poll_sys)
+    }
+
+    /// Declares a command-line argument, parsing it right away.
+    #[inline]
+    pub fn arg(&mut self, desc: ArgDesc) -> &mut Self {
+        self.res_or(|| Args::new(vec![])).declare(desc);
+        self
+    }
+
+    /// Command-line arguments; empty if the app was created without any.
+    #[inline]
+    pub fn args(&mut self) -> &Args {
+        self.res_or(|| Args::new(vec![])).into_inner()
+    }
+
+    /// Engine settings, loaded from the file given by the command-line arguments if not inserted yet.
+    pub fn settings(&mut self) -> Mut<'_, Settings> {
+        if !self.has_res::<Settings>() {
+            let settings = Settings::from_args(self.args());
+            self.insert_res(settings);
+        }
+
+        self.res_mut::<Settings>().unwrap()
+    }
+
+    /// Registers `T` to the [`AppTypeRegistry`], so it can be (de)serialized through reflection.
+    #[inline]
+    pub fn register_type<T: GetTypeRegistration>(&mut self) -> &mut Self {
+        self.res_or(AppTypeRegistry::default).write().register::<T>();
+        self
+    }
+
+    /// Warns about entities with component `A` but not `B`, in debug builds.
+    #[inline]
+    pub fn validate_requires<A: Component, B: Component>(&mut self) -> &mut Self {
+        self.res_or(EntityValidator::default).requires::<A, B>();
+        self
+    }
+
+    /// Warns about entities with component `C` while resource `R` doesn't exist, in debug builds.
+    #[inline]
+    pub fn validate_requires_res<C: Component, R: Resource>(&mut self) -> &mut Self {
+        self.res_or(EntityValidator::default).requires_res::<C, R>();
+        self
+    }
+
+    /// Warns about the entities `check` returns, in debug builds.
+    #[inline]
+    pub fn validate(
+        &mut self,
+        desc: impl Into<String>, check: impl FnMut(&mut World) -> Vec<Entity> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.res_or(EntityValidator::default).rule(desc, check);
+        self
+    }
+
     #[inline]
     pub fn fixed_timestep_sec<T: FixedUpdateWrap>(
         &mut self,
@@ -274,19 +375,51 @@ impl App {
         self
     }
 
+    /// Registers state `S`, driven by the `NextState<S>` resource. The state machine runs right before
+    /// [`CoreStage::PreUpdate`], and enters `initial` on its first run.
+    pub fn state<S: AppState>(&mut self, initial: S) -> &mut Self {
+        if self.has_res::<CurrentState<S>>() {
+            panic!("State {} is already registered", type_name::<S>());
+        }
+
+        self
+            .insert_res(CurrentState(initial.clone()))
+            .stage_before(CoreStage::PreUpdate, StateStage::<S>::default(), StateMachine::new(initial))
+    }
+
     #[inline]
-    pub fn exit_handle(&mut self, var: Arc<RwLock<Option<ExitReason>>>) -> &mut Self {
-        self.sys(CoreStage::SysPostUpdate, move |mut exit_event: EventReader<ExitEvent>| {
-            if !exit_event.is_empty() {
-                let event = exit_event.iter().next_back().unwrap();
-                *var.write() = Some(event.reason.clone());
+    pub fn enter_sys<S: AppState, Params>(&mut self, state: S, system: impl IntoSystemDescriptor<Params>) -> &mut Self {
+        self.state_machine::<S>().enter_stage(state).add_system(system);
+        self
+    }
 
-                exit_event.clear();
-            }
-        })
+    #[inline]
+    pub fn exit_sys<S: AppState, Params>(&mut self, state: S, system: impl IntoSystemDescriptor<Params>) -> &mut Self {
+        self.state_machine::<S>().exit_stage(state).add_system(system);
+        self
+    }
+
+    #[inline]
+    pub fn transition_sys<S: AppState, Params>(&mut self, from: S, to: S, system: impl IntoSystemDescriptor<Params>) -> &mut Self {
+        self.state_machine::<S>().transition_stage(from, to).add_system(system);
+        self
+    }
+
+    #[inline]
+    pub fn state_machine<S: AppState>(&mut self) -> &mut StateMachine<S> {
+        match self.schedule_mut().get_stage_mut::<StateMachine<S>>(StateStage::<S>::default()) {
+            Some(machine) => machine,
+            None => panic!("State {} is not registered", type_name::<S>()),
+        }
     }
-}
 
-pub trait Subsystem {
-    fn init(app: &mut App);
+    /// Writes the exit reason to `var` once [`ShutdownStage`] lets an exit request through.
+    #[inline]
+    pub fn exit_handle(&mut self, var: Arc<RwLock<Option<ExitReason>>>) -> &mut Self {
+        self.sys(ShutdownStage, (move |mut shutdown: ResMut<Shutdown>| {
+            if let Some(reason) = shutdown.resolve() {
+                *var.write() = Some(reason);
+            }
+        }).at_end())
+    }
 }
@@ -0,0 +1,226 @@
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+use std::{
+    env,
+    fmt::Write as _,
+    mem,
+    str::FromStr,
+};
+
+/// Describes a command-line argument, either a flag (`--windowed`) or an option taking a value (`--frames 60`,
+/// `--frames=60`).
+#[derive(Debug, Clone)]
+pub struct ArgDesc {
+    pub name: &'static str,
+    pub short: Option<char>,
+    /// Placeholder shown in the help message; `Some` if the argument takes a value.
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+}
+
+impl ArgDesc {
+    #[inline]
+    pub fn flag(name: &'static str) -> Self {
+        Self {
+            name,
+            short: None,
+            value: None,
+            help: "",
+        }
+    }
+
+    #[inline]
+    pub fn opt(name: &'static str, value: &'static str) -> Self {
+        Self {
+            name,
+            short: None,
+            value: Some(value),
+            help: "",
+        }
+    }
+
+    #[inline]
+    pub fn short(self, short: char) -> Self {
+        Self {
+            short: Some(short),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn help(self, help: &'static str) -> Self {
+        Self {
+            help,
+            ..self
+        }
+    }
+}
+
+/// Command-line arguments of the process. Engine arguments are declared up front in [`crate::core::App::new`] so
+/// subsystems can read them while initializing; games declare theirs with [`crate::core::App::arg`].
+#[derive(Resource, Debug)]
+pub struct Args {
+    raw: Vec<String>,
+    /// Index of `--` in `raw`, after which every argument is positional.
+    end: usize,
+    consumed: Vec<bool>,
+
+    descs: Vec<ArgDesc>,
+    matches: HashMap<&'static str, Vec<String>>,
+    errors: Vec<String>,
+}
+
+impl Args {
+    /// Arguments of the current process, with engine arguments declared.
+    #[inline]
+    pub fn from_env() -> Self {
+        Self::new(env::args().skip(1).collect())
+    }
+
+    pub fn new(raw: Vec<String>) -> Self {
+        let end = raw.iter().position(|arg| arg == "--").unwrap_or(raw.len());
+        let mut args = Self {
+            consumed: vec![false; raw.len()],
+            raw, end,
+
+            descs: vec![],
+            matches: HashMap::default(),
+            errors: vec![],
+        };
+
+        args.declare(ArgDesc::flag("help").short('h').help("Prints this help message"));
+        args.declare(ArgDesc::opt("asset-folder", "PATH").help("Reads assets from this folder"));
+        args.declare(ArgDesc::opt("settings", "PATH").help("Reads and writes settings from this file"));
+        args.declare(ArgDesc::opt("set", "KEY=VALUE").help("Overrides a setting, e.g. `window.vsync=false`"));
+        args.declare(ArgDesc::opt("log-level", "LEVEL").help("Sets the log level, from `off` to `trace`"));
+        args.declare(ArgDesc::flag("windowed").help("Disables fullscreen"));
+        args.declare(ArgDesc::flag("headless").help("Runs without a window nor rendering"));
+        args.declare(ArgDesc::opt("frames", "N").help("Exits after running N frames"));
+        args
+    }
+
+    /// Declares and parses an argument. Panics if another argument with the same name is declared.
+    pub fn declare(&mut self, desc: ArgDesc) {
+        if self.descs.iter().any(|other| other.name == desc.name || (desc.short.is_some() && other.short == desc.short)) {
+            panic!("Argument --{} is already declared", desc.name);
+        }
+
+        let long = format!("--{}", desc.name);
+        let short = desc.short.map(|short| format!("-{}", short));
+
+        let mut i = 0;
+        while i < self.end {
+            let arg = &self.raw[i];
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+
+            if name != long && Some(name) != short.as_deref() {
+                i += 1;
+                continue;
+            }
+
+            self.consumed[i] = true;
+            let values = self.matches.entry(desc.name).or_default();
+
+            if desc.value.is_some() {
+                match inline {
+                    Some(value) => values.push(value),
+                    None => if i + 1 < self.end {
+                        i += 1;
+                        self.consumed[i] = true;
+                        values.push(self.raw[i].clone());
+                    } else {
+                        self.errors.push(format!("Argument {} expects a value", name));
+                    },
+                }
+            }
+
+            i += 1;
+        }
+
+        self.descs.push(desc);
+    }
+
+    /// Returns `true` if the argument was passed at all.
+    #[inline]
+    pub fn flag(&self, name: &str) -> bool {
+        self.matches.contains_key(name)
+    }
+
+    /// The last value passed to the argument.
+    #[inline]
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values(name).last().map(String::as_str)
+    }
+
+    #[inline]
+    pub fn values(&self, name: &str) -> &[String] {
+        self.matches.get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Parses the last value passed to the argument, warning if it's invalid.
+    pub fn parse<T: FromStr>(&self, name: &str) -> Option<T> {
+        let value = self.value(name)?;
+        match value.parse() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                log::warn!("Invalid value `{}` for argument --{}", value, name);
+                None
+            },
+        }
+    }
+
+    /// Arguments not consumed by any declaration, including everything after `--`.
+    pub fn positional(&self) -> impl Iterator<Item = &str> {
+        self.raw
+            .iter()
+            .enumerate()
+            .filter(|&(i, arg)| i > self.end || (i < self.end && !self.consumed[i] && !arg.starts_with('-')))
+            .map(|(_, arg)| arg.as_str())
+    }
+
+    /// Takes errors encountered while parsing, to be reported once logging is available.
+    #[inline]
+    pub fn take_errors(&mut self) -> Vec<String> {
+        mem::take(&mut self.errors)
+    }
+
+    /// Dash-prefixed arguments that no declaration matched.
+    pub fn unknown(&self) -> impl Iterator<Item = &str> {
+        self.raw[..self.end]
+            .iter()
+            .enumerate()
+            .filter(|&(i, arg)| !self.consumed[i] && arg.starts_with('-'))
+            .map(|(_, arg)| arg.as_str())
+    }
+
+    pub fn help(&self) -> String {
+        let usages = self.descs
+            .iter()
+            .map(|desc| {
+                let mut usage = String::new();
+                match desc.short {
+                    Some(short) => { let _ = write!(usage, "-{}, ", short); },
+                    None => usage.push_str("    "),
+                }
+
+                let _ = write!(usage, "--{}", desc.name);
+                if let Some(value) = desc.value {
+                    let _ = write!(usage, " <{}>", value);
+                }
+
+                usage
+            })
+            .collect::<Vec<_>>();
+
+        let width = usages.iter().map(String::len).max().unwrap_or(0);
+        let mut help = String::from("Options:\n");
+        for (usage, desc) in usages.iter().zip(&self.descs) {
+            let _ = writeln!(help, "  {:width$}  {}", usage, desc.help, width = width);
+        }
+
+        help
+    }
+}
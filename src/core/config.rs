@@ -1,3 +1,4 @@
+use crate::core::Settings;
 use bevy_ecs::prelude::*;
 use std::borrow::Cow;
 
@@ -14,3 +15,24 @@ pub struct TaskPoolConf {
     pub stack_size: Option<usize>,
     pub thread_name: Option<Cow<'static, str>>,
 }
+
+impl TaskPoolConfig {
+    /// Reads `task_pool.{async_compute, compute, io}.{threads, stack_size}`.
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        self.async_pool.apply_settings(settings, "task_pool.async_compute");
+        self.compute_pool.apply_settings(settings, "task_pool.compute");
+        self.io_pool.apply_settings(settings, "task_pool.io");
+    }
+}
+
+impl TaskPoolConf {
+    pub fn apply_settings(&mut self, settings: &Settings, prefix: &str) {
+        if let Some(threads) = settings.get(&format!("{}.threads", prefix)) {
+            self.threads = Some(threads);
+        }
+
+        if let Some(stack_size) = settings.get(&format!("{}.stack_size", prefix)) {
+            self.stack_size = Some(stack_size);
+        }
+    }
+}
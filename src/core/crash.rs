@@ -0,0 +1,165 @@
+use crate::core::{
+    Time, Subsystems,
+};
+use bevy_ecs::prelude::*;
+use parking_lot::Mutex;
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    collections::VecDeque,
+    fmt::Write as _,
+    fs,
+    panic::{
+        self,
+        PanicInfo,
+    },
+    path::PathBuf,
+    process,
+    sync::Arc,
+    thread,
+    time::{
+        SystemTime, UNIX_EPOCH,
+    },
+};
+
+static LOG_HISTORY: Mutex<VecDeque<String>> = parking_lot::const_mutex(VecDeque::new());
+
+/// Configures the panic hook installed by [`crate::core::CoreSubsystem`]. Must be inserted before the subsystem
+/// is initialized.
+#[derive(Resource, Clone)]
+pub struct CrashConfig {
+    pub enabled: bool,
+    /// Folder to write crash reports into; created if it doesn't exist.
+    pub dir: PathBuf,
+    /// How many of the latest log lines to include in the report.
+    pub log_lines: usize,
+    /// Process exit code used after a crash report is written.
+    pub exit_code: i32,
+}
+
+impl Default for CrashConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: PathBuf::from("crash-reports"),
+            log_lines: 64,
+            exit_code: 70,
+        }
+    }
+}
+
+#[derive(Default)]
+struct CrashContext {
+    time: Option<Time>,
+    subsystems: Vec<&'static str>,
+}
+
+/// Keeps a snapshot of the app's state around so the panic hook can write it to a crash report.
+#[derive(Resource)]
+pub struct CrashReporter {
+    context: Arc<Mutex<CrashContext>>,
+}
+
+impl CrashReporter {
+    pub fn update_sys(reporter: Res<Self>, time: Res<Time>, subsystems: Option<Res<Subsystems>>) {
+        let mut context = reporter.context.lock();
+        context.time = Some(time.clone());
+
+        if let Some(subsystems) = subsystems {
+            if subsystems.is_changed() {
+                context.subsystems = subsystems.iter().map(|id| id.name()).collect();
+            }
+        }
+    }
+
+    /// Records a formatted log line to be included in crash reports.
+    pub fn record_log(line: String) {
+        let mut history = LOG_HISTORY.lock();
+        if history.len() >= 1024 {
+            history.pop_front();
+        }
+
+        history.push_back(line);
+    }
+
+    pub fn install(config: CrashConfig) -> Self {
+        let context = Arc::new(Mutex::new(CrashContext::default()));
+        let hook_context = Arc::clone(&context);
+
+        let prev_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            prev_hook(info);
+
+            let report = Self::report(info, &hook_context, config.log_lines);
+            let path = config.dir.join(format!("crash-{}.txt", SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_millis())
+                .unwrap_or(0)
+            ));
+
+            match fs::create_dir_all(&config.dir).and_then(|_| fs::write(&path, report)) {
+                Ok(_) => log::error!("Crash report written to {:?}", &path),
+                Err(err) => log::error!("Couldn't write crash report to {:?}: {}", &path, err),
+            }
+
+            process::exit(config.exit_code);
+        }));
+
+        Self { context }
+    }
+
+    /// Extracts the message of a panic payload, if it's a string.
+    pub fn panic_message(payload: &(dyn Any + Send)) -> &str {
+        if let Some(msg) = payload.downcast_ref::<&'static str>() {
+            msg
+        } else if let Some(msg) = payload.downcast_ref::<String>() {
+            msg
+        } else {
+            "Box<dyn Any>"
+        }
+    }
+
+    fn report(info: &PanicInfo, context: &Mutex<CrashContext>, log_lines: usize) -> String {
+        let mut report = String::new();
+
+        // Writing to a `String` never fails.
+        let _ = writeln!(report, "Thread '{}' panicked: {}", thread::current().name().unwrap_or("<unnamed>"), Self::panic_message(info.payload()));
+        if let Some(location) = info.location() {
+            let _ = writeln!(report, "Location: {}", location);
+        }
+
+        // The hook may run while the context is being updated; don't deadlock in that case.
+        match context.try_lock() {
+            Some(context) => {
+                let _ = writeln!(report, "\n== Time ==");
+                match context.time {
+                    Some(ref time) => {
+                        let _ = writeln!(report, "Frame: {}", time.frame_count());
+                        let _ = writeln!(report, "{:#?}", time);
+                    },
+                    None => {
+                        let _ = writeln!(report, "Not updated yet");
+                    },
+                }
+
+                let _ = writeln!(report, "\n== Subsystems ==");
+                for name in &context.subsystems {
+                    let _ = writeln!(report, "{}", name);
+                }
+            },
+            None => {
+                let _ = writeln!(report, "\nApp state is unavailable");
+            },
+        }
+
+        let _ = writeln!(report, "\n== Log ==");
+        if let Some(history) = LOG_HISTORY.try_lock() {
+            for line in history.iter().skip(history.len().saturating_sub(log_lines)) {
+                let _ = writeln!(report, "{}", line);
+            }
+        }
+
+        let _ = writeln!(report, "\n== Backtrace ==\n{}", Backtrace::force_capture());
+        report
+    }
+}
@@ -13,10 +13,35 @@ impl ExitEvent {
     pub fn error(msg: impl Into<Cow<'static, str>>) -> Self {
         Self { reason: ExitReason::Error(msg.into()) }
     }
+
+    pub fn code(code: i32) -> Self {
+        Self { reason: ExitReason::Code(code) }
+    }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub enum ExitReason {
     Graceful,
     Error(Cow<'static, str>),
+    /// Exits with a custom process exit code.
+    Code(i32),
+}
+
+impl ExitReason {
+    #[inline]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Graceful => 0,
+            Self::Error(_) => 1,
+            Self::Code(code) => *code,
+        }
+    }
+
+    pub fn log(&self) {
+        match self {
+            Self::Graceful => log::info!("App exited gracefully"),
+            Self::Error(msg) => log::error!("App exited with error: {}", msg),
+            Self::Code(code) => log::info!("App exited with code {}", code),
+        }
+    }
 }
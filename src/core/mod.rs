@@ -8,18 +8,36 @@ use bevy_tasks::{
 };
 
 mod app;
+mod args;
 mod config;
+mod crash;
 mod event;
 mod fixed_time;
+mod reflect;
+mod settings;
+mod shutdown;
+mod state;
+mod subsystem;
 mod sys;
+mod task;
 mod time;
+mod validation;
 
 pub use app::*;
+pub use args::*;
 pub use config::*;
+pub use crash::*;
 pub use event::*;
 pub use fixed_time::*;
+pub use reflect::*;
+pub use settings::*;
+pub use shutdown::*;
+pub use state::*;
+pub use subsystem::*;
 pub use sys::*;
+pub use task::*;
 pub use time::*;
+pub use validation::*;
 
 pub mod re_exports {
     pub use ::bevy_ecs;
@@ -31,11 +49,18 @@ pub mod prelude {
     pub use crate::core::{
         re_exports::*,
         CoreSubsystem,
-        App, Subsystem,
+        App, Args, ArgDesc,
+        Subsystem, SubsystemId, SubsystemGroup, Subsystems,
+        Settings,
         TaskPoolConfig, TaskPoolConf,
-        ExitEvent, ExitReason,
-        StartupStage, CoreStage, CoreLabel,
-        Time, FixedUpdate, FixedUpdateWrap,
+        CrashConfig, CrashReporter,
+        TaskHandle, TaskOutput, MainThread,
+        EntityValidator,
+        AppTypeRegistry,
+        ExitEvent, ExitReason, Shutdown,
+        StartupStage, ShutdownStage, CoreStage, CoreLabel,
+        AppState, StateStage, StateScoped, StateMachine,
+        Time, FrameLimit, FixedUpdate, FixedUpdateWrap,
     };
 
     pub use bevy_ecs::{
@@ -44,6 +69,7 @@ pub mod prelude {
             EntityMap,
             MapEntities, MapEntitiesError,
         },
+        reflect::ReflectMapEntities,
         event::Event,
         schedule::{
             ShouldRun,
@@ -73,7 +99,16 @@ pub mod prelude {
 pub struct CoreSubsystem;
 impl Subsystem for CoreSubsystem {
     fn init(app: &mut App) {
-        let config = app.res_or(TaskPoolConfig::default);
+        let mut settings = app.settings();
+        for err in settings.take_errors() {
+            log::warn!("{}", err);
+        }
+
+        let settings = settings.clone();
+        let mut config = app.res_or(TaskPoolConfig::default);
+        config.apply_settings(&settings);
+
+        let config = config.clone();
         let create = |conf: &TaskPoolConf| {
             let mut builder = TaskPoolBuilder::new();
             builder = if let Some(num) = conf.threads {
@@ -95,6 +130,7 @@ impl Subsystem for CoreSubsystem {
         ComputeTaskPool::init(|| create(&config.compute_pool));
         IoTaskPool::init(|| create(&config.io_pool));
 
+        let crash = app.res_or(CrashConfig::default).clone();
         app
             .stage(CoreStage::SysUpdate, SystemStage::parallel()
                 .with_system(Time::update_sys.label(CoreLabel::TimeUpdate))
@@ -109,9 +145,40 @@ impl Subsystem for CoreSubsystem {
             .stage(CoreStage::PostUpdate, SystemStage::parallel())
             .stage(CoreStage::SysPostUpdate, SystemStage::parallel()
                 .with_system(bevy_tasks::tick_global_task_pools_on_main_thread.at_end())
+                .with_system(Shutdown::request_sys.at_end())
+                .with_system(MainThread::run_sys.at_end())
+            )
+            .stage(ShutdownStage, SystemStage::parallel()
+                .with_run_criteria(Shutdown::requested_sys)
+                .with_system(Settings::save_sys)
             )
 
             .init_res::<Time>()
+            .init_res::<Shutdown>()
+            .init_res::<MainThread>()
+            .init_res::<EntityValidator>()
+            .init_res::<AppTypeRegistry>()
             .event::<ExitEvent>();
+
+        if cfg!(debug_assertions) {
+            app.sys(CoreStage::PostUpdate, EntityValidator::validate_sys
+                .label(CoreLabel::EntityValidation)
+                .at_end()
+            );
+        }
+
+        if let Some(frames) = app.args().parse::<u64>("frames") {
+            app.insert_res(FrameLimit(frames));
+        }
+
+        if app.has_res::<FrameLimit>() {
+            app.sys(CoreStage::SysPostUpdate, FrameLimit::exit_sys);
+        }
+
+        if crash.enabled {
+            app
+                .insert_res(CrashReporter::install(crash))
+                .sys(CoreStage::SysUpdate, CrashReporter::update_sys.after(CoreLabel::TimeUpdate));
+        }
     }
 }
@@ -0,0 +1,8 @@
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypeRegistryArc;
+use derive_more::*;
+
+/// The app-wide type registry, shared with asset loaders and scenes. Register types with
+/// [`App::register_type`](crate::core::App::register_type).
+#[derive(Resource, Clone, Default, Debug, Deref, DerefMut)]
+pub struct AppTypeRegistry(pub TypeRegistryArc);
@@ -0,0 +1,244 @@
+use crate::core::Args;
+use bevy_ecs::prelude::*;
+use serde::{
+    de::DeserializeOwned,
+    Serialize,
+};
+use std::{
+    env,
+    fs,
+    io,
+    mem,
+    path::{
+        Path, PathBuf,
+    },
+};
+use toml::{
+    value::Table,
+    Value,
+};
+
+/// Engine settings read from a TOML file at startup, e.g. `window.vsync` or `log.level`. Values may be overridden
+/// by environment variables (`AVOCADO__WINDOW__VSYNC=false`) and command-line arguments (`--set window.vsync=false`);
+/// overrides are never written back. Insert one before initializing any subsystem to use another file.
+#[derive(Resource, Debug, Clone)]
+pub struct Settings {
+    path: Option<PathBuf>,
+    file: Table,
+    overrides: Table,
+    dirty: bool,
+    errors: Vec<String>,
+}
+
+impl Settings {
+    pub const FILE_NAME: &'static str = "settings.toml";
+    pub const ENV_PREFIX: &'static str = "AVOCADO__";
+
+    /// Writes the settings file back if anything changed; runs in [`crate::core::ShutdownStage`].
+    pub fn save_sys(mut settings: ResMut<Self>) {
+        if settings.dirty {
+            if let Err(err) = settings.save() {
+                log::warn!("Couldn't save settings: {}", err);
+            }
+        }
+    }
+
+    /// Settings without a backing file nor overrides.
+    pub fn empty() -> Self {
+        Self {
+            path: None,
+            file: Table::new(),
+            overrides: Table::new(),
+            dirty: false,
+            errors: vec![],
+        }
+    }
+
+    /// Loads [`Settings::FILE_NAME`] next to the executable, with overrides from the environment.
+    pub fn load_default() -> Self {
+        let mut settings = Self::load(Self::default_path());
+        settings.override_env();
+        settings
+    }
+
+    /// Loads the file given by `--settings` or the default one, with overrides from the environment, then from
+    /// `--set`, `--log-level` and `--windowed`.
+    pub fn from_args(args: &Args) -> Self {
+        let mut settings = Self::load(args.value("settings").map(PathBuf::from).unwrap_or_else(Self::default_path));
+        settings.override_env();
+
+        for pair in args.values("set") {
+            settings.override_pair(pair);
+        }
+
+        if let Some(level) = args.value("log-level") {
+            settings.override_raw("log.level", level);
+        }
+
+        if args.flag("windowed") {
+            settings.override_raw("window.fullscreen", "windowed");
+        }
+
+        settings
+    }
+
+    /// Loads the file at `path`, which is created on save if it doesn't exist yet.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let mut settings = Self::empty();
+
+        match fs::read_to_string(&path) {
+            Ok(source) => match source.parse::<Value>() {
+                Ok(Value::Table(table)) => settings.file = table,
+                Ok(_) => settings.errors.push(format!("{:?} isn't a table", &path)),
+                Err(err) => settings.errors.push(format!("Couldn't parse {:?}: {}", &path, err)),
+            },
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {},
+            Err(err) => settings.errors.push(format!("Couldn't read {:?}: {}", &path, err)),
+        }
+
+        settings.path = Some(path);
+        settings
+    }
+
+    pub fn default_path() -> PathBuf {
+        env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(|dir| dir.join(Self::FILE_NAME)))
+            .unwrap_or_else(|| PathBuf::from(Self::FILE_NAME))
+    }
+
+    #[inline]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Applies `AVOCADO__SECTION__KEY=value` environment variables as overrides of `section.key`.
+    pub fn override_env(&mut self) {
+        for (name, value) in env::vars() {
+            if let Some(key) = name.strip_prefix(Self::ENV_PREFIX) {
+                let key = key.to_lowercase().replace("__", ".");
+                self.override_raw(&key, &value);
+            }
+        }
+    }
+
+    /// Applies a `key=value` pair as an override.
+    pub fn override_pair(&mut self, pair: &str) {
+        match pair.split_once('=') {
+            Some((key, value)) => self.override_raw(key.trim(), value.trim()),
+            None => self.errors.push(format!("Invalid setting override `{}`; expected `key=value`", pair)),
+        }
+    }
+
+    /// Overrides `key` with `value` parsed as a TOML value, or as a plain string if that fails.
+    pub fn override_raw(&mut self, key: &str, value: &str) {
+        let value = match format!("v = {}", value).parse::<Value>() {
+            Ok(Value::Table(mut table)) => table.remove("v").unwrap_or_else(|| Value::String(value.to_string())),
+            _ => Value::String(value.to_string()),
+        };
+
+        Self::insert(&mut self.overrides, key, value);
+    }
+
+    /// Gets a dotted `key`, e.g. `window.width`, preferring overrides over the file.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = Self::lookup(&self.overrides, key).or_else(|| Self::lookup(&self.file, key))?;
+        match value.clone().try_into() {
+            Ok(value) => Some(value),
+            Err(err) => {
+                log::warn!("Invalid setting `{}`: {}", key, err);
+                None
+            },
+        }
+    }
+
+    /// Sets a dotted `key` to be written back on save, replacing any override of it.
+    pub fn set<T: Serialize>(&mut self, key: &str, value: T) {
+        let value = match Value::try_from(value) {
+            Ok(value) => value,
+            Err(err) => {
+                log::warn!("Couldn't set setting `{}`: {}", key, err);
+                return;
+            },
+        };
+
+        Self::remove(&mut self.overrides, key);
+        if Self::lookup(&self.file, key) != Some(&value) {
+            Self::insert(&mut self.file, key, value);
+            self.dirty = true;
+        }
+    }
+
+    #[inline]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Takes errors encountered while loading, to be reported once logging is available.
+    #[inline]
+    pub fn take_errors(&mut self) -> Vec<String> {
+        mem::take(&mut self.errors)
+    }
+
+    /// Writes the file to a temporary path first, then renames it over the settings file.
+    pub fn save(&mut self) -> Result<(), anyhow::Error> {
+        let Some(path) = self.path.clone() else { return Ok(()) };
+
+        let source = toml::to_string_pretty(&self.file)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, source)?;
+        fs::rename(&temp, &path)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn lookup<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+        let mut parts = key.split('.');
+        let mut value = table.get(parts.next()?)?;
+        for part in parts {
+            value = value.as_table()?.get(part)?;
+        }
+
+        Some(value)
+    }
+
+    fn insert(table: &mut Table, key: &str, value: Value) {
+        let (parents, last) = match key.rsplit_once('.') {
+            Some((parents, last)) => (Some(parents), last),
+            None => (None, key),
+        };
+
+        let mut table = table;
+        for part in parents.into_iter().flat_map(|parents| parents.split('.')) {
+            let entry = table.entry(part.to_string()).or_insert_with(|| Value::Table(Table::new()));
+            if !entry.is_table() {
+                *entry = Value::Table(Table::new());
+            }
+
+            table = entry.as_table_mut().unwrap();
+        }
+
+        table.insert(last.to_string(), value);
+    }
+
+    fn remove(table: &mut Table, key: &str) {
+        let mut parts = key.split('.').collect::<Vec<_>>();
+        let Some(last) = parts.pop() else { return };
+
+        let mut table = table;
+        for part in parts {
+            match table.get_mut(part).and_then(Value::as_table_mut) {
+                Some(next) => table = next,
+                None => return,
+            }
+        }
+
+        table.remove(last);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::core::{
+    ExitEvent, ExitReason,
+};
+use bevy_ecs::{
+    prelude::*,
+    schedule::ShouldRun,
+};
+
+/// Tracks a pending exit request. Systems in [`crate::core::ShutdownStage`] may inspect it to flush data, and
+/// [`cancel`](Shutdown::cancel) or [`delay`](Shutdown::delay) the exit if needed.
+#[derive(Resource, Default)]
+pub struct Shutdown {
+    reason: Option<ExitReason>,
+    cancelled: bool,
+    delayed: bool,
+    frames: usize,
+}
+
+impl Shutdown {
+    pub fn request_sys(mut shutdown: ResMut<Self>, mut exit_event: EventReader<ExitEvent>) {
+        if !exit_event.is_empty() {
+            let event = exit_event.iter().next_back().unwrap();
+            shutdown.request(event.reason.clone());
+
+            exit_event.clear();
+        }
+    }
+
+    pub fn requested_sys(shutdown: Res<Self>) -> ShouldRun {
+        if shutdown.is_requested() {
+            ShouldRun::Yes
+        } else {
+            ShouldRun::No
+        }
+    }
+
+    /// Requests an exit. If one is already pending, only the reason is replaced.
+    pub fn request(&mut self, reason: ExitReason) {
+        if self.reason.is_none() {
+            self.frames = 0;
+        }
+
+        self.reason = Some(reason);
+    }
+
+    /// Vetoes the pending exit request.
+    #[inline]
+    pub fn cancel(&mut self) {
+        self.cancelled = true;
+    }
+
+    /// Keeps the exit request pending for another frame; the shutdown stage will run again next frame.
+    #[inline]
+    pub fn delay(&mut self) {
+        self.delayed = true;
+    }
+
+    /// Consumes the verdict of the shutdown stage, returning the exit reason if nothing cancelled or delayed it.
+    pub fn resolve(&mut self) -> Option<ExitReason> {
+        let Some(ref reason) = self.reason else { return None };
+
+        let result = if self.cancelled {
+            log::info!("Exit request cancelled");
+            self.reason = None;
+            None
+        } else if self.delayed {
+            self.frames += 1;
+            None
+        } else {
+            Some(reason.clone())
+        };
+
+        self.cancelled = false;
+        self.delayed = false;
+        result
+    }
+
+    #[inline]
+    pub fn is_requested(&self) -> bool {
+        self.reason.is_some()
+    }
+
+    #[inline]
+    pub fn reason(&self) -> Option<&ExitReason> {
+        self.reason.as_ref()
+    }
+
+    /// How many frames the exit request has been delayed for.
+    #[inline]
+    pub fn delayed_frames(&self) -> usize {
+        self.frames
+    }
+}
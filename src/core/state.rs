@@ -0,0 +1,108 @@
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+use iyes_loopless::state::{
+    CurrentState, NextState,
+};
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+};
+
+pub trait AppState: 'static + Clone + PartialEq + Eq + Hash + Debug + Send + Sync {}
+impl<T: 'static + Clone + PartialEq + Eq + Hash + Debug + Send + Sync> AppState for T {}
+
+/// Label of the [`StateMachine`] stage of state `S`, which runs right before [`crate::core::CoreStage::PreUpdate`].
+#[derive(StageLabel)]
+#[stage_label(ignore_fields)]
+pub struct StateStage<S: AppState>(PhantomData<fn() -> S>);
+
+impl<S: AppState> Default for StateStage<S> {
+    #[inline]
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Entities with this component are despawned when their state is exited.
+#[derive(Component, Debug, Clone)]
+pub struct StateScoped<S: AppState>(pub S);
+
+/// Applies [`NextState`] requests, running exit, transition, and enter stages respectively.
+pub struct StateMachine<S: AppState> {
+    initial: Option<S>,
+
+    enter: HashMap<S, SystemStage>,
+    exit: HashMap<S, SystemStage>,
+    transition: HashMap<(S, S), SystemStage>,
+}
+
+impl<S: AppState> StateMachine<S> {
+    pub fn new(initial: S) -> Self {
+        Self {
+            initial: Some(initial),
+
+            enter: HashMap::default(),
+            exit: HashMap::default(),
+            transition: HashMap::default(),
+        }
+    }
+
+    #[inline]
+    pub fn enter_stage(&mut self, state: S) -> &mut SystemStage {
+        self.enter.entry(state).or_insert_with(SystemStage::parallel)
+    }
+
+    #[inline]
+    pub fn exit_stage(&mut self, state: S) -> &mut SystemStage {
+        self.exit.entry(state).or_insert_with(SystemStage::parallel)
+    }
+
+    #[inline]
+    pub fn transition_stage(&mut self, from: S, to: S) -> &mut SystemStage {
+        self.transition.entry((from, to)).or_insert_with(SystemStage::parallel)
+    }
+
+    fn despawn_scoped(world: &mut World, state: &S) {
+        let entities = world
+            .query::<(Entity, &StateScoped<S>)>()
+            .iter(world)
+            .filter_map(|(entity, scope)| if &scope.0 == state { Some(entity) } else { None })
+            .collect::<Vec<_>>();
+
+        for entity in entities {
+            world.despawn(entity);
+        }
+    }
+}
+
+impl<S: AppState> Stage for StateMachine<S> {
+    fn run(&mut self, world: &mut World) {
+        if let Some(initial) = self.initial.take() {
+            world.insert_resource(CurrentState(initial.clone()));
+            if let Some(stage) = self.enter.get_mut(&initial) {
+                stage.run(world);
+            }
+        }
+
+        while let Some(NextState(next)) = world.remove_resource::<NextState<S>>() {
+            let prev = world.resource::<CurrentState<S>>().0.clone();
+            log::debug!("State transition: {:?} -> {:?}", &prev, &next);
+
+            if let Some(stage) = self.exit.get_mut(&prev) {
+                stage.run(world);
+            }
+
+            Self::despawn_scoped(world, &prev);
+            world.insert_resource(CurrentState(next.clone()));
+
+            if let Some(stage) = self.transition.get_mut(&(prev, next.clone())) {
+                stage.run(world);
+            }
+
+            if let Some(stage) = self.enter.get_mut(&next) {
+                stage.run(world);
+            }
+        }
+    }
+}
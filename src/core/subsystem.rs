@@ -0,0 +1,264 @@
+use crate::core::App;
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+use std::{
+    any::{
+        type_name, TypeId,
+    },
+    fmt,
+    hash::{
+        Hash, Hasher,
+    },
+};
+
+pub trait Subsystem: 'static {
+    fn init(app: &mut App);
+
+    /// Subsystems that have to be initialized before this one.
+    #[inline]
+    fn deps() -> Vec<SubsystemId> {
+        vec![]
+    }
+}
+
+/// Type-based identifier of a [`Subsystem`].
+#[derive(Copy, Clone)]
+pub struct SubsystemId {
+    id: TypeId,
+    name: &'static str,
+}
+
+impl SubsystemId {
+    #[inline]
+    pub fn of<T: Subsystem>() -> Self {
+        Self {
+            id: TypeId::of::<T>(),
+            name: type_name::<T>(),
+        }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl PartialEq for SubsystemId {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for SubsystemId {}
+
+impl Hash for SubsystemId {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl fmt::Debug for SubsystemId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+impl fmt::Display for SubsystemId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+#[derive(Copy, Clone)]
+pub(crate) struct SubsystemDesc {
+    pub id: SubsystemId,
+    /// The subsystem this one stands in for, if it's a replacement.
+    pub provides: SubsystemId,
+    pub init: fn(&mut App),
+    pub deps: fn() -> Vec<SubsystemId>,
+}
+
+impl SubsystemDesc {
+    #[inline]
+    pub fn of<T: Subsystem>() -> Self {
+        Self {
+            id: SubsystemId::of::<T>(),
+            provides: SubsystemId::of::<T>(),
+            init: T::init,
+            deps: T::deps,
+        }
+    }
+}
+
+/// Keeps track of every initialized subsystem, in initialization order.
+#[derive(Resource, Default)]
+pub struct Subsystems {
+    order: Vec<SubsystemId>,
+    provided: HashMap<SubsystemId, SubsystemId>,
+}
+
+impl Subsystems {
+    /// Returns `true` if the subsystem, or a replacement of it, is initialized.
+    #[inline]
+    pub fn contains(&self, id: SubsystemId) -> bool {
+        self.provided.contains_key(&id)
+    }
+
+    /// Returns the subsystem that actually got initialized in place of `id`, if any.
+    #[inline]
+    pub fn provider(&self, id: SubsystemId) -> Option<SubsystemId> {
+        self.provided.get(&id).copied()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = SubsystemId> + '_ {
+        self.order.iter().copied()
+    }
+
+    pub(crate) fn register(&mut self, desc: &SubsystemDesc) {
+        for id in [desc.id, desc.provides] {
+            if let Some(by) = self.provided.get(&id) {
+                if *by == id {
+                    panic!("Subsystem {} is already initialized", id);
+                } else {
+                    panic!("Subsystem {} is already initialized by {}", id, by);
+                }
+            }
+        }
+
+        for dep in (desc.deps)() {
+            if !self.contains(dep) {
+                panic!("Subsystem {} requires {} to be initialized first", desc.id, dep);
+            }
+        }
+
+        self.order.push(desc.id);
+        self.provided.insert(desc.id, desc.id);
+        self.provided.insert(desc.provides, desc.id);
+    }
+}
+
+/// A set of subsystems initialized together, sorted by their dependencies. Members may be disabled or replaced
+/// before the group gets initialized.
+#[derive(Default)]
+pub struct SubsystemGroup {
+    entries: Vec<(SubsystemDesc, bool)>,
+}
+
+impl SubsystemGroup {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add<T: Subsystem>(mut self) -> Self {
+        let desc = SubsystemDesc::of::<T>();
+        if self.index(desc.id).is_some() {
+            panic!("Subsystem {} is already in the group", desc.id);
+        }
+
+        self.entries.push((desc, true));
+        self
+    }
+
+    pub fn add_group(mut self, group: SubsystemGroup) -> Self {
+        for (desc, enabled) in group.entries {
+            if self.index(desc.provides).is_some() {
+                panic!("Subsystem {} is already in the group", desc.provides);
+            }
+
+            self.entries.push((desc, enabled));
+        }
+
+        self
+    }
+
+    pub fn disable<T: Subsystem>(mut self) -> Self {
+        let index = self.index_or_panic(SubsystemId::of::<T>());
+        self.entries[index].1 = false;
+        self
+    }
+
+    pub fn enable<T: Subsystem>(mut self) -> Self {
+        let index = self.index_or_panic(SubsystemId::of::<T>());
+        self.entries[index].1 = true;
+        self
+    }
+
+    /// Replaces `T` with `R`. Other subsystems depending on `T` will be satisfied by `R` instead.
+    pub fn replace<T: Subsystem, R: Subsystem>(mut self) -> Self {
+        let index = self.index_or_panic(SubsystemId::of::<T>());
+
+        let (desc, _) = &mut self.entries[index];
+        *desc = SubsystemDesc {
+            provides: desc.provides,
+            ..SubsystemDesc::of::<R>()
+        };
+
+        self
+    }
+
+    #[inline]
+    pub fn contains<T: Subsystem>(&self) -> bool {
+        self.index(SubsystemId::of::<T>()).is_some()
+    }
+
+    pub fn init(self, app: &mut App) {
+        let entries = self.entries.into_iter()
+            .filter_map(|(desc, enabled)| if enabled { Some(desc) } else { None })
+            .collect::<Vec<_>>();
+
+        let mut indices = HashMap::default();
+        for (i, desc) in entries.iter().enumerate() {
+            indices.insert(desc.provides, i);
+        }
+
+        // 0 = unvisited, 1 = visiting, 2 = visited.
+        fn visit(
+            i: usize,
+            entries: &[SubsystemDesc], indices: &HashMap<SubsystemId, usize>,
+            marks: &mut [u8], sorted: &mut Vec<usize>,
+        ) {
+            match marks[i] {
+                2 => return,
+                1 => panic!("Subsystem {} circles with itself", entries[i].id),
+                _ => {},
+            }
+
+            marks[i] = 1;
+            for dep in (entries[i].deps)() {
+                if let Some(&dep) = indices.get(&dep) {
+                    visit(dep, entries, indices, marks, sorted);
+                }
+            }
+
+            marks[i] = 2;
+            sorted.push(i);
+        }
+
+        let mut marks = vec![0; entries.len()];
+        let mut sorted = Vec::with_capacity(entries.len());
+        for i in 0..entries.len() {
+            visit(i, &entries, &indices, &mut marks, &mut sorted);
+        }
+
+        for i in sorted {
+            app.init_desc(entries[i]);
+        }
+    }
+
+    #[inline]
+    fn index(&self, id: SubsystemId) -> Option<usize> {
+        self.entries.iter().position(|(desc, _)| desc.provides == id || desc.id == id)
+    }
+
+    #[inline]
+    fn index_or_panic(&self, id: SubsystemId) -> usize {
+        match self.index(id) {
+            Some(index) => index,
+            None => panic!("Subsystem {} is not in the group", id),
+        }
+    }
+}
@@ -3,6 +3,10 @@ use bevy_ecs::prelude::*;
 #[derive(StageLabel)]
 pub struct StartupStage;
 
+/// Runs after an exit is requested, before the app actually exits. See [`crate::core::Shutdown`].
+#[derive(StageLabel)]
+pub struct ShutdownStage;
+
 #[derive(StageLabel)]
 pub enum CoreStage {
     /// Time, input poll, and events update.
@@ -21,5 +25,9 @@ pub enum CoreLabel {
     EventUpdate,
     EntityValidation,
 
+    GamepadPoll,
+    InputDevice,
+    InputUpdate,
+
     ComputeTransform,
 }
@@ -0,0 +1,132 @@
+use bevy_ecs::prelude::*;
+use bevy_tasks::{
+    Task,
+    AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool,
+};
+use crossbeam_channel::{
+    Sender, Receiver,
+};
+use futures_lite::future;
+use std::future::Future;
+
+pub type MainThreadCallback = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// A task spawned on one of the global task pools. Use it as a component or resource and [`poll`](TaskHandle::poll)
+/// it each frame. Dropping the handle cancels the task, so despawning its entity cancels it as well.
+#[derive(Component, Resource)]
+pub struct TaskHandle<T: 'static + Send + Sync> {
+    task: Option<Task<T>>,
+}
+
+impl<T: 'static + Send + Sync> TaskHandle<T> {
+    /// Polls every [`TaskHandle<T>`] component, replacing it with [`TaskOutput<T>`] once it finishes.
+    pub fn poll_sys(mut commands: Commands, mut handles: Query<(Entity, &mut TaskHandle<T>)>) {
+        for (entity, mut handle) in &mut handles {
+            if let Some(output) = handle.poll() {
+                commands.entity(entity)
+                    .remove::<TaskHandle<T>>()
+                    .insert(TaskOutput(output));
+            }
+        }
+    }
+
+    #[inline]
+    pub fn new(task: Task<T>) -> Self {
+        Self { task: Some(task) }
+    }
+
+    #[inline]
+    pub fn async_compute(future: impl Future<Output = T> + Send + 'static) -> Self {
+        Self::new(AsyncComputeTaskPool::get().spawn(future))
+    }
+
+    #[inline]
+    pub fn compute(future: impl Future<Output = T> + Send + 'static) -> Self {
+        Self::new(ComputeTaskPool::get().spawn(future))
+    }
+
+    #[inline]
+    pub fn io(future: impl Future<Output = T> + Send + 'static) -> Self {
+        Self::new(IoTaskPool::get().spawn(future))
+    }
+
+    /// Returns the task's output if it has finished. The output is only ever returned once.
+    pub fn poll(&mut self) -> Option<T> {
+        let task = self.task.as_mut()?;
+        if !task.is_finished() {
+            return None;
+        }
+
+        let output = future::block_on(future::poll_once(task));
+        if output.is_some() {
+            self.task = None;
+        }
+
+        output
+    }
+
+    /// Returns `true` if the task has finished, whether or not its output has been taken.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        match self.task {
+            Some(ref task) => task.is_finished(),
+            None => true,
+        }
+    }
+
+    /// Cancels the task explicitly. Equivalent to dropping the handle.
+    #[inline]
+    pub fn cancel(&mut self) {
+        self.task = None;
+    }
+}
+
+/// Inserted in place of [`TaskHandle<T>`] by [`TaskHandle::poll_sys`] once the task finishes.
+#[derive(Component, Debug)]
+pub struct TaskOutput<T: 'static + Send + Sync>(pub T);
+
+/// A queue of closures to run on the main thread with exclusive [`World`] access, drained every frame at the end of
+/// [`crate::core::CoreStage::SysPostUpdate`]. Clone it to send closures from other threads or tasks.
+#[derive(Resource, Clone)]
+pub struct MainThread {
+    sender: Sender<MainThreadCallback>,
+    receiver: Receiver<MainThreadCallback>,
+}
+
+impl MainThread {
+    pub fn run_sys(world: &mut World) {
+        let receiver = world.resource::<Self>().receiver.clone();
+        while let Ok(callback) = receiver.try_recv() {
+            callback(world);
+        }
+    }
+
+    /// Schedules `callback` to run on the main thread later.
+    pub fn schedule(&self, callback: impl FnOnce(&mut World) + Send + Sync + 'static) {
+        if let Err(msg) = self.sender.send(Box::new(callback)) {
+            log::warn!("Couldn't schedule main thread callback: {}", msg);
+        }
+    }
+
+    /// Schedules `callback` to run on the main thread, returning a future that resolves to its output. Mostly useful
+    /// for tasks that need to touch the [`World`] midway.
+    pub fn run<R: 'static + Send>(
+        &self, callback: impl FnOnce(&mut World) -> R + Send + Sync + 'static,
+    ) -> impl Future<Output = Result<R, async_channel::RecvError>> {
+        let (sender, receiver) = async_channel::bounded(1);
+        self.schedule(move |world| {
+            if sender.try_send(callback(world)).is_err() {
+                log::warn!("Main thread callback result was discarded");
+            }
+        });
+
+        async move { receiver.recv().await }
+    }
+}
+
+impl Default for MainThread {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self { sender, receiver, }
+    }
+}
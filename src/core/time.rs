@@ -1,9 +1,10 @@
+use crate::core::ExitEvent;
 use bevy_ecs::prelude::*;
 use std::time::{
     Duration, Instant
 };
 
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Debug, Clone)]
 pub struct Time {
     first_update: Option<Instant>,
     last_update: Option<Instant>,
@@ -19,8 +20,10 @@ pub struct Time {
     elapsed_sec: f64,
     elapsed_no_pause_sec: f64,
 
+    frame_count: u64,
     pausing: bool,
     unpausing: bool,
+    next_delta: Option<Duration>,
 }
 
 impl Time {
@@ -37,7 +40,12 @@ impl Time {
     }
 
     pub fn update(&mut self) {
-        let now = Instant::now();
+        let now = match (self.next_delta.take(), self.last_update) {
+            (Some(delta), Some(last_update)) => last_update + delta,
+            _ => Instant::now(),
+        };
+
+        self.frame_count += 1;
 
         if self.first_update.is_none() {
             self.first_update = Some(now);
@@ -94,6 +102,18 @@ impl Time {
         }
     }
 
+    /// Makes the next update advance by `delta` instead of the real time elapsed, e.g. to replay a recording.
+    #[inline]
+    pub fn set_next_delta(&mut self, delta: Duration) {
+        self.next_delta = Some(delta);
+    }
+
+    /// How many times this has been updated, i.e. the current frame number starting from 1.
+    #[inline]
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
     #[inline]
     pub fn first_update(&self) -> Option<Instant> {
         self.first_update
@@ -169,3 +189,15 @@ impl Time {
         self.elapsed_no_pause_sec
     }
 }
+
+/// Exits the app once [`Time::frame_count`] reaches the given number of frames, e.g. from `--frames N`.
+#[derive(Resource, Debug, Copy, Clone)]
+pub struct FrameLimit(pub u64);
+
+impl FrameLimit {
+    pub fn exit_sys(limit: Res<Self>, time: Res<Time>, mut exit: EventWriter<ExitEvent>) {
+        if time.frame_count() >= limit.0 {
+            exit.send(ExitEvent::graceful());
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use bevy_ecs::prelude::*;
+use bevy_utils::HashSet;
+use std::any::type_name;
+
+pub type ValidationCheck = Box<dyn FnMut(&mut World) -> Vec<Entity> + Send + Sync>;
+
+/// Checks entity invariants registered by subsystems, warning once for every entity that violates them. Only
+/// runs in debug builds, in [`crate::core::CoreLabel::EntityValidation`].
+#[derive(Resource, Default)]
+pub struct EntityValidator {
+    rules: Vec<(String, ValidationCheck)>,
+    reported: HashSet<(Entity, usize)>,
+}
+
+impl EntityValidator {
+    pub fn validate_sys(world: &mut World) {
+        world.resource_scope(|world, mut validator: Mut<Self>| validator.validate(world));
+    }
+
+    /// Registers a rule; `check` returns every entity violating it.
+    pub fn rule(&mut self, desc: impl Into<String>, check: impl FnMut(&mut World) -> Vec<Entity> + Send + Sync + 'static) {
+        self.rules.push((desc.into(), Box::new(check)));
+    }
+
+    /// Entities with component `A` must also have component `B`.
+    pub fn requires<A: Component, B: Component>(&mut self) {
+        let mut query = None::<QueryState<Entity, (With<A>, Without<B>)>>;
+        self.rule(format!("{} requires component {}", type_name::<A>(), type_name::<B>()), move |world| query
+            .get_or_insert_with(|| world.query_filtered())
+            .iter(world)
+            .collect()
+        );
+    }
+
+    /// Resource `R` must exist whenever an entity has component `C`.
+    pub fn requires_res<C: Component, R: Resource>(&mut self) {
+        let mut query = None::<QueryState<Entity, With<C>>>;
+        self.rule(format!("{} requires resource {}", type_name::<C>(), type_name::<R>()), move |world| {
+            if world.contains_resource::<R>() {
+                vec![]
+            } else {
+                query
+                    .get_or_insert_with(|| world.query_filtered())
+                    .iter(world)
+                    .collect()
+            }
+        });
+    }
+
+    pub fn validate(&mut self, world: &mut World) {
+        self.reported.retain(|(entity, _)| world.get_entity(*entity).is_some());
+
+        for (i, (desc, check)) in self.rules.iter_mut().enumerate() {
+            for entity in check(world) {
+                if self.reported.insert((entity, i)) {
+                    let components = world
+                        .inspect_entity(entity)
+                        .iter()
+                        .map(|info| info.name())
+                        .collect::<Vec<_>>();
+
+                    log::warn!("Entity {:?} is invalid: {}. Components: {:?}", entity, desc, components);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{
+    core::Time,
+    diagnostic::DiagnosticConfig,
+};
+use bevy_ecs::{
+    prelude::*,
+    entity::Entities,
+};
+use bevy_utils::Instant;
+use std::{
+    borrow::Cow,
+    fmt::Write as _,
+    collections::{
+        BTreeMap, VecDeque,
+    },
+};
+
+/// A named time series of measurements, e.g. frames per second.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    name: Cow<'static, str>,
+    suffix: &'static str,
+    history: VecDeque<(Instant, f64)>,
+    capacity: usize,
+    smoothing: f64,
+    smoothed: Option<f64>,
+}
+
+impl Diagnostic {
+    pub fn new(name: impl Into<Cow<'static, str>>, suffix: &'static str, capacity: usize, smoothing: f64) -> Self {
+        Self {
+            name: name.into(),
+            suffix,
+            history: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            smoothing: smoothing.clamp(0., 1.),
+            smoothed: None,
+        }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Unit appended to values when logged, e.g. `ms`.
+    #[inline]
+    pub fn suffix(&self) -> &'static str {
+        self.suffix
+    }
+
+    pub fn add(&mut self, value: f64) {
+        if self.history.len() >= self.capacity {
+            self.history.pop_front();
+        }
+
+        self.history.push_back((Instant::now(), value));
+        self.smoothed = Some(match self.smoothed {
+            Some(smoothed) => smoothed + (value - smoothed) * self.smoothing,
+            None => value,
+        });
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.history.len() > self.capacity {
+            self.history.pop_front();
+        }
+    }
+
+    /// Measurements with the time they were taken, oldest first.
+    #[inline]
+    pub fn history(&self) -> &VecDeque<(Instant, f64)> {
+        &self.history
+    }
+
+    #[inline]
+    pub fn value(&self) -> Option<f64> {
+        self.history.back().map(|&(_, value)| value)
+    }
+
+    /// Exponential moving average of every measurement.
+    #[inline]
+    pub fn smoothed(&self) -> Option<f64> {
+        self.smoothed
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.history.iter().map(|&(_, value)| value).reduce(f64::min)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.history.iter().map(|&(_, value)| value).reduce(f64::max)
+    }
+
+    pub fn average(&self) -> Option<f64> {
+        if self.history.is_empty() {
+            None
+        } else {
+            Some(self.history.iter().map(|&(_, value)| value).sum::<f64>() / self.history.len() as f64)
+        }
+    }
+}
+
+/// Registry of every [`Diagnostic`], measured by the systems of the subsystems that register them.
+#[derive(Resource, Debug, Clone)]
+pub struct Diagnostics {
+    diagnostics: BTreeMap<Cow<'static, str>, Diagnostic>,
+    capacity: usize,
+    smoothing: f64,
+}
+
+impl Default for Diagnostics {
+    fn default() -> Self {
+        Self {
+            diagnostics: BTreeMap::new(),
+            capacity: 120,
+            smoothing: 0.1,
+        }
+    }
+}
+
+impl Diagnostics {
+    pub const FPS: &'static str = "fps";
+    pub const FRAME_TIME: &'static str = "frame_time";
+    pub const ENTITY_COUNT: &'static str = "entity_count";
+
+    pub fn frame_sys(time: Res<Time>, mut diagnostics: ResMut<Self>) {
+        let delta = time.delta_sec_f64();
+        if delta > 0. {
+            diagnostics.add(Self::FPS, 1. / delta);
+            diagnostics.add(Self::FRAME_TIME, delta * 1000.);
+        }
+    }
+
+    pub fn entity_sys(entities: &Entities, mut diagnostics: ResMut<Self>) {
+        diagnostics.add(Self::ENTITY_COUNT, entities.len() as f64);
+    }
+
+    /// Logs every diagnostic each [`DiagnosticConfig::log_interval`] seconds, if set.
+    pub fn log_sys(
+        time: Res<Time>, config: Res<DiagnosticConfig>, diagnostics: Res<Self>,
+        mut last: Local<f64>,
+    ) {
+        let Some(interval) = config.log_interval else { return };
+
+        let now = time.elapsed_no_pause_sec_f64();
+        if now - *last < interval {
+            return;
+        }
+
+        *last = now;
+
+        let width = diagnostics.iter().map(|diagnostic| diagnostic.name().len()).max().unwrap_or(0);
+        let mut message = String::from("Diagnostics:");
+        for diagnostic in diagnostics.iter() {
+            let (Some(smoothed), Some(min), Some(average), Some(max)) = (
+                diagnostic.smoothed(), diagnostic.min(), diagnostic.average(), diagnostic.max(),
+            ) else { continue };
+
+            let _ = write!(
+                message, "\n  {:width$}  {:>10.2}{:3} (min {:.2}, avg {:.2}, max {:.2})",
+                diagnostic.name(), smoothed, diagnostic.suffix(), min, average, max,
+                width = width,
+            );
+        }
+
+        log::info!("{}", message);
+    }
+
+    /// Registers a diagnostic if there isn't one with the same name yet.
+    pub fn register(&mut self, name: impl Into<Cow<'static, str>>, suffix: &'static str) {
+        let name = name.into();
+        if !self.diagnostics.contains_key(&name) {
+            self.diagnostics.insert(name.clone(), Diagnostic::new(name, suffix, self.capacity, self.smoothing));
+        }
+    }
+
+    /// Adds a measurement to a registered diagnostic; does nothing if it isn't registered.
+    #[inline]
+    pub fn add(&mut self, name: &str, value: f64) {
+        if let Some(diagnostic) = self.diagnostics.get_mut(name) {
+            diagnostic.add(value);
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Diagnostic> {
+        self.diagnostics.get(name)
+    }
+
+    /// Every diagnostic, sorted by name.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.values()
+    }
+
+    /// Sets how many measurements each diagnostic keeps and how much new ones weigh in the smoothed value, for
+    /// existing and future diagnostics.
+    pub fn configure(&mut self, capacity: usize, smoothing: f64) {
+        self.capacity = capacity.max(1);
+        self.smoothing = smoothing.clamp(0., 1.);
+
+        for diagnostic in self.diagnostics.values_mut() {
+            diagnostic.set_capacity(self.capacity);
+            diagnostic.smoothing = self.smoothing;
+        }
+    }
+}
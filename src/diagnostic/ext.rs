@@ -0,0 +1,16 @@
+use crate::{
+    core::prelude::*,
+    diagnostic::Diagnostics,
+};
+use std::borrow::Cow;
+
+pub trait AppExt {
+    fn diagnostic(&mut self, name: impl Into<Cow<'static, str>>, suffix: &'static str) -> &mut Self;
+}
+
+impl AppExt for App {
+    fn diagnostic(&mut self, name: impl Into<Cow<'static, str>>, suffix: &'static str) -> &mut Self {
+        self.res_or(Diagnostics::default).register(name, suffix);
+        self
+    }
+}
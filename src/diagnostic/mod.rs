@@ -0,0 +1,80 @@
+use crate::core::prelude::*;
+
+mod diagnostic;
+mod ext;
+
+pub use diagnostic::*;
+pub use ext::*;
+
+pub mod prelude {
+    pub use crate::diagnostic::{
+        DiagnosticSubsystem, AppExt as _,
+        DiagnosticConfig, Diagnostic, Diagnostics,
+    };
+}
+
+/// Configures [`DiagnosticSubsystem`]. Must be inserted before the subsystem is initialized.
+#[derive(Resource, Debug, Clone)]
+pub struct DiagnosticConfig {
+    /// How many measurements each diagnostic keeps.
+    pub capacity: usize,
+    /// How much a new measurement weighs in the smoothed value, from 0 to 1.
+    pub smoothing: f64,
+    /// Logs every diagnostic at this interval in seconds, if set.
+    pub log_interval: Option<f64>,
+}
+
+impl Default for DiagnosticConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 120,
+            smoothing: 0.1,
+            log_interval: None,
+        }
+    }
+}
+
+impl DiagnosticConfig {
+    /// Reads `diagnostic.capacity`, `diagnostic.smoothing` and `diagnostic.log_interval`; a non-positive interval
+    /// disables logging.
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        if let Some(capacity) = settings.get::<usize>("diagnostic.capacity") {
+            self.capacity = capacity;
+        }
+
+        if let Some(smoothing) = settings.get::<f64>("diagnostic.smoothing") {
+            self.smoothing = smoothing;
+        }
+
+        if let Some(interval) = settings.get::<f64>("diagnostic.log_interval") {
+            self.log_interval = (interval > 0.).then_some(interval);
+        }
+    }
+}
+
+/// Measures frame rate, frame time and entity count. Other subsystems register their own diagnostics, e.g. loaded
+/// assets per type or sprite batch draw calls.
+pub struct DiagnosticSubsystem;
+impl Subsystem for DiagnosticSubsystem {
+    fn init(app: &mut App) {
+        let settings = app.settings().clone();
+        let mut config = app.res_or(DiagnosticConfig::default);
+        config.apply_settings(&settings);
+
+        let config = config.clone();
+        app.res_or(Diagnostics::default).configure(config.capacity, config.smoothing);
+
+        app
+            .diagnostic(Diagnostics::FPS, "")
+            .diagnostic(Diagnostics::FRAME_TIME, "ms")
+            .diagnostic(Diagnostics::ENTITY_COUNT, "")
+
+            .sys(CoreStage::SysUpdate, Diagnostics::frame_sys.after(CoreLabel::TimeUpdate))
+            .sys(CoreStage::SysPostUpdate, Diagnostics::entity_sys)
+            .sys(CoreStage::SysPostUpdate, Diagnostics::log_sys.at_end());
+    }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![SubsystemId::of::<CoreSubsystem>()]
+    }
+}
@@ -2,6 +2,7 @@ use crate::{
     asset::prelude::*,
     core::prelude::*,
     graphics::prelude::*,
+    transform::prelude::*,
     winit::prelude::*,
     g2d::SpriteVertex,
 };
@@ -57,13 +58,15 @@ pub struct Sprite<T: SpriteVertex> {
 
 #[derive(Debug)]
 pub enum SpriteDesc<T: SpriteVertex> {
+    /// Raw vertices in world space, unaffected by the holder entity's transform.
     Direct {
         z: f32,
         vertices: SmallVec<[T; 4]>,
         indices: SmallVec<[u16; 6]>,
     },
+    /// A quad relative to the holder entity's [`GlobalTransform`](crate::transform::GlobalTransform).
     Transform {
-        pos: Vec2,
+        offset: Vec2,
         z: f32,
         anchor: Vec2,
         size: Vec2,
@@ -81,6 +84,15 @@ impl<T: SpriteVertex> SpriteDesc<T> {
         }
     }
 
+    /// The depth sprites are sorted by, adding the holder's `z` to [`SpriteDesc::Transform`] sprites only.
+    #[inline]
+    pub fn world_z(&self, transform: &GlobalTransform) -> f32 {
+        match self {
+            Self::Direct { z, .. } => *z,
+            Self::Transform { z, .. } => *z + transform.z(),
+        }
+    }
+
     #[inline]
     pub fn vert_len(&self) -> u16 {
         (match self {
@@ -98,6 +110,7 @@ impl<T: SpriteVertex> SpriteDesc<T> {
     }
 }
 
+/// Sprites positioned by this entity's [`GlobalTransform`](crate::transform::GlobalTransform), if any.
 #[derive(Component)]
 pub struct SpriteHolder<T: SpriteVertex> {
     pub sprites: Vec<Sprite<T>>,
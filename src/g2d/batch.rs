@@ -1,15 +1,18 @@
 use crate::{
     asset::prelude::*,
     core::prelude::*,
+    transform::prelude::*,
     winit::prelude::*,
     g2d::{
         SPRITE_MAX_VERTICES,
+        G2dSubsystem,
         TextureAtlas,
         SpriteHolder, SpriteVertex,
         BatchState, SpriteBatchConfig, SpriteBatchDefShader,
     },
 };
 use std::{
+    any::type_name,
     cmp::Ordering,
     fmt::Debug,
     iter,
@@ -25,13 +28,56 @@ pub struct SpriteBatch<T: SpriteVertex> {
     index_buffer: wgpu::Buffer,
     shader: Handle<Shader>,
     state: Option<BatchState>,
+
+    sprite_count: usize,
+    draw_calls: usize,
+    buffer_usage: f32,
 }
 
 impl<T: SpriteVertex> Subsystem for SpriteBatch<T> {
     fn init(app: &mut App) {
+        let mut holders = None::<QueryState<Entity, With<SpriteHolder<T>>>>;
         app.sys(RenderStage::Queue, Self::queue_sys
             .label(RenderLabel::Queue)
+        )
+        .validate_requires_res::<SpriteHolder<T>, Self>()
+        .validate(
+            format!("{} requires GlobalCamera::entity to be a camera with a visible projection", type_name::<SpriteHolder<T>>()),
+            move |world| if GlobalCamera::is_valid(world) {
+                vec![]
+            } else {
+                holders
+                    .get_or_insert_with(|| world.query_filtered())
+                    .iter(world)
+                    .collect()
+            },
         );
+
+        #[cfg(feature = "diagnostic")]
+        {
+            use crate::diagnostic::{
+                AppExt as _,
+                Diagnostics,
+            };
+
+            let name = bevy_utils::get_short_name(std::any::type_name::<Self>());
+            let [sprites, draw_calls, buffer_usage] = ["sprites", "draw_calls", "buffer_usage"].map(|stat| format!("{}.{}", name, stat));
+
+            app
+                .diagnostic(sprites.clone(), "")
+                .diagnostic(draw_calls.clone(), "")
+                .diagnostic(buffer_usage.clone(), "%")
+                .sys(RenderStage::Queue, (move |batch: Option<Res<Self>>, mut diagnostics: ResMut<Diagnostics>| {
+                    let Some(batch) = batch else { return };
+                    diagnostics.add(&sprites, batch.sprite_count as f64);
+                    diagnostics.add(&draw_calls, batch.draw_calls as f64);
+                    diagnostics.add(&buffer_usage, batch.buffer_usage as f64 * 100.);
+                }).after(RenderLabel::Queue));
+        }
+    }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![SubsystemId::of::<G2dSubsystem>()]
     }
 }
 
@@ -40,7 +86,7 @@ impl<T: SpriteVertex> SpriteBatch<T> {
         batch: Option<ResMut<Self>>,
         renderer: Res<Renderer>, camera: Res<GlobalCamera>,
         atlas: Option<Res<TextureAtlas>>, textures: Res<Assets<Texture>>, shaders: Res<Assets<Shader>>,
-        holders: Query<&SpriteHolder<T>>,
+        holders: Query<(&SpriteHolder<T>, Option<&GlobalTransform>)>,
     ) {
         let Some(mut batch) = batch else { return };
         let Some(atlas) = atlas else { return };
@@ -55,15 +101,19 @@ impl<T: SpriteVertex> SpriteBatch<T> {
         }
 
         let mut sprites = vec![];
-        for holder in &holders {
-            holder.sprites.iter().for_each(|sprite| sprites.push(sprite));
+        for (holder, transform) in &holders {
+            let transform = transform.unwrap_or(&GlobalTransform::IDENTITY);
+            holder.sprites.iter().for_each(|sprite| sprites.push((sprite, transform)));
         }
 
-        sprites.sort_by(|a, b| a.desc.z().partial_cmp(&b.desc.z()).unwrap_or(Ordering::Equal));
+        sprites.sort_by(|(a, a_trns), (b, b_trns)| a.desc.world_z(a_trns)
+            .partial_cmp(&b.desc.world_z(b_trns))
+            .unwrap_or(Ordering::Equal)
+        );
 
         let max_ind = batch.max_indices;
         let mut flush_index = 0;
-        for sprite in &sprites {
+        for (sprite, transform) in &sprites {
             let (_, _, vert_len, ind_len) = &batch.buffer_data[flush_index];
             assert!((sprite.desc.vert_len() as usize) <= SPRITE_MAX_VERTICES, "Too many vertices ({} > {})", sprite.desc.vert_len(), SPRITE_MAX_VERTICES);
             assert!(sprite.desc.ind_len() <= max_ind, "Too many indices ({} > {})", sprite.desc.ind_len(), max_ind);
@@ -82,7 +132,7 @@ impl<T: SpriteVertex> SpriteBatch<T> {
             }
 
             let (vertices, indices, vert_len, ind_len) = &mut batch.buffer_data[flush_index];
-            let (v, i) = T::from_sprite(sprite, *vert_len as u16);
+            let (v, i) = T::from_sprite(sprite, transform, *vert_len as u16);
 
             vertices[(*vert_len as usize)..(*vert_len as usize) + v.len()].copy_from_slice(&v);
             *vert_len += v.len() as u32;
@@ -90,6 +140,37 @@ impl<T: SpriteVertex> SpriteBatch<T> {
             indices[(*ind_len as usize)..(*ind_len as usize) + i.len()].copy_from_slice(&i);
             *ind_len += i.len() as u32;
         }
+
+        let flushes = batch.buffer_data
+            .iter()
+            .take_while(|(_, _, vert_len, ind_len)| *vert_len > 0 && *ind_len > 0)
+            .map(|(_, _, vert_len, ind_len)| f32::max(
+                *vert_len as f32 / SPRITE_MAX_VERTICES as f32,
+                *ind_len as f32 / max_ind as f32,
+            ))
+            .collect::<Vec<_>>();
+
+        batch.sprite_count = sprites.len();
+        batch.draw_calls = flushes.len();
+        batch.buffer_usage = flushes.into_iter().fold(0., f32::max);
+    }
+
+    /// Sprites queued this frame.
+    #[inline]
+    pub fn sprite_count(&self) -> usize {
+        self.sprite_count
+    }
+
+    /// Draw calls needed to render the sprites queued this frame, one per buffer flush.
+    #[inline]
+    pub fn draw_calls(&self) -> usize {
+        self.draw_calls
+    }
+
+    /// How full the vertex or index buffer gets in the fullest flush this frame, from 0 to 1.
+    #[inline]
+    pub fn buffer_usage(&self) -> f32 {
+        self.buffer_usage
     }
 
     pub fn create_data(max_indices: u32) -> (Vec<T>, Vec<u16>, u32, u32) {
@@ -136,6 +217,10 @@ impl<T: SpriteVertex> FromWorld for SpriteBatch<T> {
 
             vertex_buffer, index_buffer, shader,
             state: None,
+
+            sprite_count: 0,
+            draw_calls: 0,
+            buffer_usage: 0.,
         }
     }
 }
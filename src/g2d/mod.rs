@@ -44,4 +44,8 @@ impl Subsystem for G2dSubsystem {
             .asset::<TextureAtlas>()
             .asset_loader::<TextureAtlas>(TextureAtlasLoader);
     }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![SubsystemId::of::<WinitSubsystem>()]
+    }
 }
@@ -1,5 +1,6 @@
 use crate::{
     graphics::prelude::*,
+    transform::prelude::*,
     winit::prelude::*,
     g2d::{
         AtlasRegion, Sprite, SpriteDesc,
@@ -20,20 +21,23 @@ pub trait SpriteVertex: 'static + Debug + Copy + Clone + Send + Sync + Pod + Zer
     const ATTRIBUTES: &'static [wgpu::VertexAttribute];
 
     #[inline]
-    fn from_sprite(sprite: &Sprite<Self>, ind_offset: u16) -> (SmallVec<[Self; 4]>, SmallVec<[u16; 6]>) {
+    fn from_sprite(
+        sprite: &Sprite<Self>, transform: &GlobalTransform,
+        ind_offset: u16,
+    ) -> (SmallVec<[Self; 4]>, SmallVec<[u16; 6]>) {
         match &sprite.desc {
             SpriteDesc::Direct {
                 vertices, indices,
                 ..
             } => Self::from_direct(vertices, indices, ind_offset),
             SpriteDesc::Transform {
-                pos,
+                offset,
                 anchor, size, rotation,
                 data,
                 ..
             } => Self::from_transform(
-                sprite.region, sprite.color,
-                *pos, *anchor, *size, *rotation, ind_offset, data,
+                sprite.region, sprite.color, transform,
+                *offset, *anchor, *size, *rotation, ind_offset, data,
             ),
         }
     }
@@ -56,7 +60,7 @@ pub trait SpriteVertex: 'static + Debug + Copy + Clone + Send + Sync + Pod + Zer
     }
 
     fn from_transform(
-        region: AtlasRegion, color: Color,
+        region: AtlasRegion, color: Color, transform: &GlobalTransform,
         pos: Vec2, anchor: Vec2, size: Vec2, rotation: f32,
         ind_offset: u16,
         data: &Self::Data,
@@ -74,12 +78,12 @@ pub trait SpriteVertex: 'static + Debug + Copy + Clone + Send + Sync + Pod + Zer
         let color3 = copy_col(&color1);
         let color4 = copy_col(&color1);
 
-        let vertices = SmallVec::from_slice(&(if rotation.abs() <= 0.000001 {
+        let [p1, p2, p3, p4] = if rotation.abs() <= 0.000001 {
             [
-                Self::new(pos.x, pos.y, region.u, region.v2, color1, 0, page_index, data),
-                Self::new(pos.x + size.x, pos.y, region.u2, region.v2, color1, 1, page_index, data),
-                Self::new(pos.x + size.x, pos.y + size.y, region.u2, region.v, color1, 2, page_index, data),
-                Self::new(pos.x, pos.y + size.y, region.u, region.v, color1, 3, page_index, data),
+                pos,
+                Vec2 { x: pos.x + size.x, y: pos.y },
+                pos + size,
+                Vec2 { x: pos.x, y: pos.y + size.y },
             ]
         } else {
             let (sin, cos) = rotation.to_radians().sin_cos();
@@ -101,12 +105,19 @@ pub trait SpriteVertex: 'static + Debug + Copy + Clone + Send + Sync + Pod + Zer
             let y4 = y3 + (y1 - y2);
 
             [
-                Self::new(x1, y1, region.u, region.v2, color1, 0, page_index, data),
-                Self::new(x2, y2, region.u2, region.v2, color2, 1, page_index, data),
-                Self::new(x3, y3, region.u2, region.v, color3, 2, page_index, data),
-                Self::new(x4, y4, region.u, region.v, color4, 3, page_index, data),
+                Vec2 { x: x1, y: y1 },
+                Vec2 { x: x2, y: y2 },
+                Vec2 { x: x3, y: y3 },
+                Vec2 { x: x4, y: y4 },
             ]
-        }));
+        }.map(|p| transform.transform_point(p));
+
+        let vertices = SmallVec::from_slice(&[
+            Self::new(p1.x, p1.y, region.u, region.v2, color1, 0, page_index, data),
+            Self::new(p2.x, p2.y, region.u2, region.v2, color2, 1, page_index, data),
+            Self::new(p3.x, p3.y, region.u2, region.v, color3, 2, page_index, data),
+            Self::new(p4.x, p4.y, region.u, region.v, color4, 3, page_index, data),
+        ]);
 
         let indices = SmallVec::from_slice(&[
             ind_offset,
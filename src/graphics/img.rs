@@ -1,5 +1,8 @@
 use bevy_reflect::TypeUuid;
-use image::GenericImageView as _;
+use image::{
+    codecs::png::PngEncoder,
+    ColorType, GenericImageView as _, ImageEncoder as _,
+};
 use std::{
     borrow::Cow,
     path::Path,
@@ -37,6 +40,13 @@ impl Image {
         })
     }
 
+    pub fn to_png(&self) -> Result<Vec<u8>, image::error::ImageError> {
+        let mut bytes = vec![];
+        PngEncoder::new(&mut bytes).write_image(&self.data, self.width, self.height, ColorType::Rgba8)?;
+
+        Ok(bytes)
+    }
+
     pub fn draw(&mut self, other: &Image, x: u32, y: u32) -> &mut Self {
         let src_row = (other.width.min(self.width - x) as usize) * 4;
         let dst_row = (self.width as usize) * 4;
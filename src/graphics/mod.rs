@@ -35,4 +35,12 @@ impl Subsystem for GraphicsSubsystem {
             .asset::<Image>()
             .asset_loader::<Image>(ImageLoader);
     }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![
+            SubsystemId::of::<CoreSubsystem>(),
+            #[cfg(feature = "asset")]
+            SubsystemId::of::<AssetSubsystem>(),
+        ]
+    }
 }
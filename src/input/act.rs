@@ -1,7 +1,10 @@
 use crate::core::prelude::*;
 use bevy_math::Vec2;
 use bevy_utils::HashMap;
-use std::hash::Hash;
+use std::{
+    hash::Hash,
+    time::Duration,
+};
 
 pub trait InputAction: 'static + Copy + PartialEq + Eq + Hash + Send + Sync {}
 impl<T: 'static + Copy + PartialEq + Eq + Hash + Send + Sync> InputAction for T {}
@@ -11,13 +14,32 @@ impl<T: 'static + Copy + PartialEq + Eq + Hash + Send + Sync> InputAction for T
 pub enum InputValue {
     Single {
         pressed: bool,
+        /// Pressed this frame.
         tapped: bool,
+        /// Released this frame.
+        released: bool,
+        /// How long the action has been held, or was held before being released this frame.
+        held: Duration,
+        /// How many times in a row the action was tapped, each within the tap window of the previous one.
+        taps: u32,
     },
     Linear(f32),
     Axis(Vec2),
 }
 
 impl InputValue {
+    /// A single value with no interactions, filled in by [`InputManager`](crate::input::InputManager).
+    #[inline]
+    pub const fn single(pressed: bool) -> Self {
+        Self::Single {
+            pressed,
+            tapped: false,
+            released: false,
+            held: Duration::ZERO,
+            taps: 0,
+        }
+    }
+
     #[inline]
     pub fn pressed(self) -> Option<bool> {
         match self {
@@ -34,6 +56,30 @@ impl InputValue {
         }
     }
 
+    #[inline]
+    pub fn released(self) -> Option<bool> {
+        match self {
+            Self::Single { released, .. } => Some(released),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn held(self) -> Option<Duration> {
+        match self {
+            Self::Single { held, .. } => Some(held),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn taps(self) -> Option<u32> {
+        match self {
+            Self::Single { taps, .. } => Some(taps),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub fn linear(self) -> Option<f32> {
         match self {
@@ -58,22 +104,57 @@ pub struct InputState<T: InputAction> {
 
 impl<T: InputAction> InputState<T> {
     #[inline]
-    pub fn pressed(self, key: &T) -> Option<bool> {
-        self.values.get(key).map(|val| val.pressed()).unwrap_or(None)
+    pub fn get(&self, key: &T) -> Option<InputValue> {
+        self.values.get(key).copied()
+    }
+
+    #[inline]
+    pub fn pressed(&self, key: &T) -> Option<bool> {
+        self.get(key).and_then(InputValue::pressed)
+    }
+
+    #[inline]
+    pub fn tapped(&self, key: &T) -> Option<bool> {
+        self.get(key).and_then(InputValue::tapped)
+    }
+
+    #[inline]
+    pub fn released(&self, key: &T) -> Option<bool> {
+        self.get(key).and_then(InputValue::released)
+    }
+
+    /// How long the action has been held so far.
+    #[inline]
+    pub fn held(&self, key: &T) -> Option<Duration> {
+        self.get(key).and_then(|val| Some(if val.pressed()? { val.held()? } else { Duration::ZERO }))
+    }
+
+    /// Whether the action is held and has been for at least `duration`.
+    #[inline]
+    pub fn held_for(&self, key: &T, duration: Duration) -> Option<bool> {
+        self.get(key).and_then(|val| Some(val.pressed()? && val.held()? >= duration))
+    }
+
+    /// Whether the action was tapped at least twice in a row this frame, see
+    /// [`InputBindings::set_tap_window`](crate::input::InputBindings::set_tap_window).
+    #[inline]
+    pub fn double_tapped(&self, key: &T) -> Option<bool> {
+        self.get(key).and_then(|val| Some(val.tapped()? && val.taps()? >= 2))
     }
 
+    /// Whether the action was released this frame after being held for at least `duration`, e.g. a charged attack.
     #[inline]
-    pub fn tapped(self, key: &T) -> Option<bool> {
-        self.values.get(key).map(|val| val.tapped()).unwrap_or(None)
+    pub fn charged(&self, key: &T, duration: Duration) -> Option<bool> {
+        self.get(key).and_then(|val| Some(val.released()? && val.held()? >= duration))
     }
 
     #[inline]
-    pub fn linear(self, key: &T) -> Option<f32> {
-        self.values.get(key).map(|val| val.linear()).unwrap_or(None)
+    pub fn linear(&self, key: &T) -> Option<f32> {
+        self.get(key).and_then(InputValue::linear)
     }
 
     #[inline]
-    pub fn axis(self, key: &T) -> Option<Vec2> {
-        self.values.get(key).map(|val| val.axis()).unwrap_or(None)
+    pub fn axis(&self, key: &T) -> Option<Vec2> {
+        self.get(key).and_then(InputValue::axis)
     }
 }
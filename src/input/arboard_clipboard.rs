@@ -0,0 +1,33 @@
+use crate::input::ClipboardBackend;
+
+/// The system [`ClipboardBackend`], backed by [arboard](https://github.com/1Password/arboard).
+pub struct ArboardClipboard {
+    clipboard: arboard::Clipboard,
+}
+
+impl ArboardClipboard {
+    pub fn new() -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            clipboard: arboard::Clipboard::new()?,
+        })
+    }
+}
+
+impl ClipboardBackend for ArboardClipboard {
+    fn get(&mut self) -> Option<String> {
+        match self.clipboard.get_text() {
+            Ok(text) => Some(text),
+            Err(arboard::Error::ContentNotAvailable) => None,
+            Err(err) => {
+                log::warn!("Couldn't read the clipboard: {}", err);
+                None
+            },
+        }
+    }
+
+    fn set(&mut self, text: String) {
+        if let Err(err) = self.clipboard.set_text(text) {
+            log::warn!("Couldn't write to the clipboard: {}", err);
+        }
+    }
+}
@@ -1,29 +1,37 @@
 use crate::{
     core::prelude::*,
     input::{
-        KeyCode,
-        InputAction, InputValue,
+        KeyCode, InputButton, Modifiers,
+        InputAction, InputValue, InputProcessor, InputSequence, RawInput,
+        GamepadButton, GamepadAxis,
     },
 };
 use bevy_math::Vec2;
-use bevy_utils::{
-    HashMap, HashSet
+use bevy_utils::HashMap;
+use serde::{
+    Deserialize, Serialize,
 };
 use smallvec::SmallVec;
-use std::iter;
+use std::time::Duration;
 
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum InputBinding {
-    Single(SmallVec<[KeyCode; 2]>),
+    Single(SmallVec<[InputButton; 2]>),
+    Chord(SmallVec<[InputChord; 2]>),
     Linear(SmallVec<[InputLinear; 2]>),
     Axis(SmallVec<[InputAxis; 2]>),
 }
 
 impl InputBinding {
     #[inline]
-    pub fn single(keys: &[KeyCode]) -> Self {
-        Self::Single(SmallVec::from_slice(keys))
+    pub fn single(buttons: impl IntoIterator<Item = impl Into<InputButton>>) -> Self {
+        Self::Single(buttons.into_iter().map(Into::into).collect())
+    }
+
+    #[inline]
+    pub fn chord(chords: impl IntoIterator<Item = InputChord>) -> Self {
+        Self::Chord(chords.into_iter().collect())
     }
 
     #[inline]
@@ -36,52 +44,111 @@ impl InputBinding {
         Self::Axis(SmallVec::from_slice(axis))
     }
 
-    pub fn keys(&self) -> Vec<KeyCode> {
+    pub fn buttons(&self) -> Vec<InputButton> {
         match self {
             Self::Single(single) => single.iter().copied().collect(),
-            Self::Linear(linear) => linear.iter().flat_map(|i|
-                iter::once(i.positive)
-                .chain(iter::once(i.negative))
-            ).collect(),
-            Self::Axis(axis) => axis.iter().flat_map(|i| match i {
-                InputAxis::Keys { up, down, left, right, } =>
-                    iter::once(*up)
-                    .chain(iter::once(*down))
-                    .chain(iter::once(*left))
-                    .chain(iter::once(*right))
+            Self::Chord(chord) => chord.iter().flat_map(|i| i.buttons.iter().copied()).collect(),
+            Self::Linear(linear) => linear.iter().flat_map(|i| match *i {
+                InputLinear::Keys { positive, negative, } => vec![positive, negative],
+                InputLinear::Analog(_) => vec![],
+            }).collect(),
+            Self::Axis(axis) => axis.iter().flat_map(|i| match *i {
+                InputAxis::Keys { up, down, left, right, } => vec![up, down, left, right],
+                InputAxis::Analog { .. } => vec![],
             }).collect(),
         }
     }
 
-    pub fn value(
-        &self,
-        key_down: &HashSet<KeyCode>, key_tapped: &HashSet<KeyCode>,
-    ) -> InputValue {
+    pub fn analogs(&self) -> Vec<InputAnalog> {
         match self {
-            Self::Single(single) => {
-                for input in single {
-                    if key_down.contains(input) {
-                        return InputValue::Single {
-                            pressed: true,
-                            tapped: key_tapped.contains(input),
-                        };
-                    }
-                }
+            Self::Single(_) | Self::Chord(_) => vec![],
+            Self::Linear(linear) => linear.iter().flat_map(|i| match *i {
+                InputLinear::Keys { .. } => vec![],
+                InputLinear::Analog(analog) => vec![analog],
+            }).collect(),
+            Self::Axis(axis) => axis.iter().flat_map(|i| match *i {
+                InputAxis::Keys { .. } => vec![],
+                InputAxis::Analog { x, y, } => vec![x, y],
+            }).collect(),
+        }
+    }
 
-                InputValue::Single {
-                    pressed: false,
-                    tapped: false,
-                }
-            },
+    /// Chords of this binding, if any.
+    pub fn chords(&self) -> &[InputChord] {
+        match self {
+            Self::Chord(chord) => chord,
+            _ => &[],
+        }
+    }
+
+    /// A binding of the same kind without any inputs.
+    pub fn cleared(&self) -> Self {
+        match self {
+            Self::Single(_) => Self::Single(SmallVec::new()),
+            Self::Chord(_) => Self::Chord(SmallVec::new()),
+            Self::Linear(_) => Self::Linear(SmallVec::new()),
+            Self::Axis(_) => Self::Axis(SmallVec::new()),
+        }
+    }
+
+    /// Whether the binding has no inputs.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Single(single) => single.is_empty(),
+            Self::Chord(chord) => chord.is_empty(),
+            Self::Linear(linear) => linear.is_empty(),
+            Self::Axis(axis) => axis.is_empty(),
+        }
+    }
+
+    /// Whether both bindings share an input. Chords only conflict with the same chord, as the more specific of
+    /// overlapping ones takes precedence anyway.
+    pub fn conflicts_with(&self, other: &Self) -> bool {
+        let plain = |binding: &Self| match binding {
+            Self::Chord(_) => vec![],
+            _ => binding.buttons(),
+        };
+
+        let other_plain = plain(other);
+        let other_analogs = other.analogs();
+
+        plain(self).iter().any(|button| other_plain.contains(button)) ||
+        self.chords().iter().any(|chord| other.chords().contains(chord)) ||
+        self.analogs().iter().any(|analog| other_analogs.contains(analog))
+    }
+
+    /// Evaluates the binding; the first of its inputs that is still active after processing wins. Keys taken by a
+    /// more specific active chord count as released. Interactions such as taps are left for
+    /// [`InputManager`](crate::input::InputManager) to track.
+    pub fn value(&self, raw: &RawInput, processor: &InputProcessor) -> InputValue {
+        let key = |button| raw.available(button, 1);
+        let analog = |input| raw.analog(input);
+        match self {
+            Self::Single(single) => InputValue::single(single.iter().any(|&input| key(input))),
+            Self::Chord(chord) => InputValue::single(chord.iter().any(|input|
+                input.is_held(raw) &&
+                input.buttons.iter().all(|&button| raw.available(button, input.specificity()))
+            )),
             Self::Linear(linear) => {
                 for input in linear {
-                    let add = key_down.contains(&input.positive);
-                    let sub = key_down.contains(&input.negative);
-                    if add || sub {
-                        return InputValue::Linear(
-                            (if add { 1. } else { 0. }) -
-                            (if sub { 1. } else { 0. })
-                        )
+                    match input {
+                        InputLinear::Keys { positive, negative, } => {
+                            let add = key(*positive);
+                            let sub = key(*negative);
+                            if add || sub {
+                                return InputValue::Linear(processor.linear(
+                                    (if add { 1. } else { 0. }) -
+                                    (if sub { 1. } else { 0. }),
+                                    false,
+                                ))
+                            }
+                        },
+                        InputLinear::Analog(input) => {
+                            let value = processor.linear(analog(*input), true);
+                            if value != 0. {
+                                return InputValue::Linear(value);
+                            }
+                        },
                     }
                 }
 
@@ -91,22 +158,28 @@ impl InputBinding {
                 for input in axis {
                     match input {
                         InputAxis::Keys { up, down, left, right, } => {
-                            let up = key_down.contains(up);
-                            let down = key_down.contains(down);
-                            let left = key_down.contains(left);
-                            let right = key_down.contains(right);
+                            let up = key(*up);
+                            let down = key(*down);
+                            let left = key(*left);
+                            let right = key(*right);
 
                             if up || down || left || right {
-                                return InputValue::Axis(Vec2 {
+                                return InputValue::Axis(processor.axis(Vec2 {
                                     x:
                                         (if right { 1. } else { 0. }) -
                                         (if left { 1. } else { 0. }),
                                     y:
                                         (if up { 1. } else { 0. }) -
                                         (if down { 1. } else { 0. }),
-                                }.normalize())
+                                }, false))
+                            }
+                        },
+                        InputAxis::Analog { x, y, } => {
+                            let value = processor.axis(Vec2::new(analog(*x), analog(*y)), true);
+                            if value != Vec2::ZERO {
+                                return InputValue::Axis(value);
                             }
-                        }
+                        },
                     }
                 }
 
@@ -116,46 +189,310 @@ impl InputBinding {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-pub struct InputLinear {
-    pub positive: KeyCode,
-    pub negative: KeyCode,
+/// Buttons held together while the required modifiers are held, e.g. `Ctrl+S`. Holding extra modifiers still
+/// counts, but a more specific chord bound elsewhere takes precedence.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct InputChord {
+    pub modifiers: Modifiers,
+    pub buttons: SmallVec<[InputButton; 2]>,
+}
+
+impl InputChord {
+    #[inline]
+    pub fn new(modifiers: Modifiers, buttons: impl IntoIterator<Item = impl Into<InputButton>>) -> Self {
+        Self {
+            modifiers,
+            buttons: buttons.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Several buttons held together, without modifiers.
+    #[inline]
+    pub fn buttons(buttons: impl IntoIterator<Item = impl Into<InputButton>>) -> Self {
+        Self::new(Modifiers::NONE, buttons)
+    }
+
+    /// How many modifiers and buttons the chord requires; the more, the higher its precedence.
+    #[inline]
+    pub fn specificity(&self) -> usize {
+        self.modifiers.len() + self.buttons.len()
+    }
+
+    /// Whether every required modifier and button is held, regardless of other chords.
+    #[inline]
+    pub fn is_held(&self, raw: &RawInput) -> bool {
+        !self.buttons.is_empty() &&
+        raw.modifiers().contains(self.modifiers) &&
+        self.buttons.iter().all(|&button| raw.pressed(button))
+    }
+}
+
+/// A continuous input. Mouse inputs are reset to 0 every frame they aren't reported, gamepad axes hold their last
+/// value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputAnalog {
+    /// Raw mouse motion, in device units.
+    MouseMotionX,
+    MouseMotionY,
+    /// Mouse wheel scroll, in lines.
+    MouseWheelX,
+    MouseWheelY,
+    /// An axis of any connected gamepad, the one furthest from rest winning.
+    Gamepad(GamepadAxis),
+}
+
+impl From<GamepadAxis> for InputAnalog {
+    #[inline]
+    fn from(axis: GamepadAxis) -> Self {
+        Self::Gamepad(axis)
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InputLinear {
+    Keys {
+        positive: InputButton,
+        negative: InputButton,
+    },
+    Analog(InputAnalog),
 }
 
 impl InputLinear {
     #[inline]
-    pub fn new(positive: KeyCode, negative: KeyCode) -> Self {
-        Self { positive, negative, }
+    pub fn new(positive: impl Into<InputButton>, negative: impl Into<InputButton>) -> Self {
+        Self::Keys {
+            positive: positive.into(),
+            negative: negative.into(),
+        }
+    }
+
+    #[inline]
+    pub fn analog(analog: impl Into<InputAnalog>) -> Self {
+        Self::Analog(analog.into())
     }
 }
 
 #[non_exhaustive]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum InputAxis {
     Keys {
-        up: KeyCode,
-        down: KeyCode,
-        left: KeyCode,
-        right: KeyCode,
+        up: InputButton,
+        down: InputButton,
+        left: InputButton,
+        right: InputButton,
+    },
+    Analog {
+        x: InputAnalog,
+        y: InputAnalog,
     },
 }
 
 impl InputAxis {
     #[inline]
-    pub fn keys(up: KeyCode, down: KeyCode, left: KeyCode, right: KeyCode) -> Self {
-        Self::Keys { up, down, left, right }
+    pub fn keys(
+        up: impl Into<InputButton>, down: impl Into<InputButton>,
+        left: impl Into<InputButton>, right: impl Into<InputButton>,
+    ) -> Self {
+        Self::Keys {
+            up: up.into(),
+            down: down.into(),
+            left: left.into(),
+            right: right.into(),
+        }
+    }
+
+    /// Layout-independent WASD keys.
+    #[inline]
+    pub fn wasd() -> Self {
+        Self::keys(
+            InputButton::Physical(KeyCode::W), InputButton::Physical(KeyCode::S),
+            InputButton::Physical(KeyCode::A), InputButton::Physical(KeyCode::D),
+        )
+    }
+
+    #[inline]
+    pub fn arrows() -> Self {
+        Self::keys(KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right)
+    }
+
+    #[inline]
+    pub fn analog(x: impl Into<InputAnalog>, y: impl Into<InputAnalog>) -> Self {
+        Self::Analog {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+
+    /// Raw mouse motion; positive `y` is downwards, as reported by the device.
+    #[inline]
+    pub fn mouse_motion() -> Self {
+        Self::analog(InputAnalog::MouseMotionX, InputAnalog::MouseMotionY)
+    }
+
+    #[inline]
+    pub fn mouse_wheel() -> Self {
+        Self::analog(InputAnalog::MouseWheelX, InputAnalog::MouseWheelY)
+    }
+
+    #[inline]
+    pub fn left_stick() -> Self {
+        Self::analog(GamepadAxis::LeftStickX, GamepadAxis::LeftStickY)
+    }
+
+    #[inline]
+    pub fn right_stick() -> Self {
+        Self::analog(GamepadAxis::RightStickX, GamepadAxis::RightStickY)
+    }
+
+    #[inline]
+    pub fn dpad() -> Self {
+        Self::keys(
+            GamepadButton::DPadUp, GamepadButton::DPadDown,
+            GamepadButton::DPadLeft, GamepadButton::DPadRight,
+        )
     }
 }
 
-#[derive(Resource)]
+/// Maps actions to their bindings, along with their defaults, processors and sequences. Also a component, giving an
+/// [`InputPlayer`](crate::input::InputPlayer) its own bindings.
+#[derive(Resource, Component)]
 pub struct InputBindings<T: InputAction> {
     pub(crate) map: HashMap<T, InputBinding>,
+    pub(crate) defaults: HashMap<T, InputBinding>,
+    pub(crate) processors: HashMap<T, InputProcessor>,
+    pub(crate) sequences: HashMap<T, InputSequence<T>>,
+    pub(crate) tap_window: Duration,
+}
+
+impl<T: InputAction> InputBindings<T> {
+    #[inline]
+    pub fn get(&self, act: &T) -> Option<&InputBinding> {
+        self.map.get(act)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &InputBinding)> {
+        self.map.iter()
+    }
+
+    /// Replaces the action's binding.
+    #[inline]
+    pub fn bind(&mut self, act: T, binding: InputBinding) -> &mut Self {
+        self.map.insert(act, binding);
+        self
+    }
+
+    /// Removes the action's binding entirely; see [`clear`](InputBindings::clear) to keep its kind.
+    #[inline]
+    pub fn unbind(&mut self, act: &T) -> Option<InputBinding> {
+        self.map.remove(act)
+    }
+
+    /// Removes every input of the action's binding, keeping it around as an empty binding of the same kind.
+    #[inline]
+    pub fn clear(&mut self, act: &T) {
+        if let Some(binding) = self.map.get_mut(act) {
+            *binding = binding.cleared();
+        }
+    }
+
+    /// Sets the binding [`reset`](InputBindings::reset) restores, and binds it if the action has no binding yet.
+    #[inline]
+    pub fn set_default(&mut self, act: T, binding: InputBinding) -> &mut Self {
+        self.map.entry(act).or_insert_with(|| binding.clone());
+        self.defaults.insert(act, binding);
+        self
+    }
+
+    #[inline]
+    pub fn default_binding(&self, act: &T) -> Option<&InputBinding> {
+        self.defaults.get(act)
+    }
+
+    /// Restores the action's default binding, or unbinds it if it has none.
+    pub fn reset(&mut self, act: &T) {
+        match self.defaults.get(act) {
+            Some(binding) => {
+                self.map.insert(*act, binding.clone());
+            },
+            None => {
+                self.map.remove(act);
+            },
+        }
+    }
+
+    /// Restores every default binding and unbinds every other action. Processors and sequences are kept.
+    pub fn reset_all(&mut self) {
+        self.map = self.defaults.clone();
+    }
+
+    /// Every other action whose binding shares an input with `binding`.
+    pub fn conflicts(&self, act: &T, binding: &InputBinding) -> Vec<T> {
+        self.map
+            .iter()
+            .filter(|&(other, other_binding)| other != act && binding.conflicts_with(other_binding))
+            .map(|(&other, _)| other)
+            .collect()
+    }
+
+    /// Every chord of every binding, used to resolve conflicts between them.
+    #[inline]
+    pub fn chords(&self) -> impl Iterator<Item = &InputChord> {
+        self.map.values().flat_map(InputBinding::chords)
+    }
+
+    /// The action's processor, or the default one if it has none.
+    #[inline]
+    pub fn processor(&self, act: &T) -> &InputProcessor {
+        static DEFAULT: InputProcessor = InputProcessor::DEFAULT;
+        self.processors.get(act).unwrap_or(&DEFAULT)
+    }
+
+    #[inline]
+    pub fn set_processor(&mut self, act: T, processor: InputProcessor) -> &mut Self {
+        self.processors.insert(act, processor);
+        self
+    }
+
+    #[inline]
+    pub fn remove_processor(&mut self, act: &T) -> Option<InputProcessor> {
+        self.processors.remove(act)
+    }
+
+    /// Makes `act` fire for a single frame whenever the sequence is completed.
+    #[inline]
+    pub fn set_sequence(&mut self, act: T, sequence: InputSequence<T>) -> &mut Self {
+        self.sequences.insert(act, sequence);
+        self
+    }
+
+    #[inline]
+    pub fn remove_sequence(&mut self, act: &T) -> Option<InputSequence<T>> {
+        self.sequences.remove(act)
+    }
+
+    /// How soon after a tap the next one must come to count towards a multi-tap; 250 milliseconds by default.
+    #[inline]
+    pub fn tap_window(&self) -> Duration {
+        self.tap_window
+    }
+
+    #[inline]
+    pub fn set_tap_window(&mut self, window: Duration) -> &mut Self {
+        self.tap_window = window;
+        self
+    }
 }
 
 impl<T: InputAction> Default for InputBindings<T> {
     fn default() -> Self {
         Self {
             map: HashMap::default(),
+            defaults: HashMap::default(),
+            processors: HashMap::default(),
+            sequences: HashMap::default(),
+            tap_window: Duration::from_millis(250),
         }
     }
 }
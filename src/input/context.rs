@@ -0,0 +1,173 @@
+use crate::{
+    core::prelude::*,
+    input::{
+        InputAction, InputBindings, InputButton, InputAnalog,
+    },
+};
+use bevy_utils::HashSet;
+
+/// Identifies a pushed [`InputContext`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InputContextId(u64);
+
+/// What an active context hides from the contexts below it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum InputConsume {
+    /// Lower contexts still see every input.
+    #[default]
+    None,
+    /// Every input bound in this context, e.g. a pause menu's navigation keys.
+    Bound,
+    /// Every keyboard key along with every input bound in this context, e.g. a console capturing typing.
+    Keyboard,
+    /// Every input, e.g. a modal dialog.
+    All,
+}
+
+/// A set of bindings stacked over [`InputBindings`], e.g. a pause menu over gameplay. Contexts are evaluated from
+/// the highest priority down, each seeing inputs the ones above it didn't consume, with the base bindings last. An
+/// action bound in several contexts takes its value from the highest one.
+pub struct InputContext<T: InputAction> {
+    pub bindings: InputBindings<T>,
+    pub consume: InputConsume,
+    priority: i32,
+}
+
+impl<T: InputAction> InputContext<T> {
+    #[inline]
+    pub fn new(bindings: InputBindings<T>, priority: i32, consume: InputConsume) -> Self {
+        Self {
+            bindings,
+            consume,
+            priority,
+        }
+    }
+
+    #[inline]
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+}
+
+/// Stack of active [`InputContext`]s. Contexts pushed while their inputs are held don't see them as tapped until
+/// they're released, and inputs consumed by a context stay hidden from the ones below until released, even if it's
+/// popped in the meantime.
+#[derive(Resource)]
+pub struct InputContexts<T: InputAction> {
+    next_id: u64,
+    /// Sorted from the highest priority down; among equal priorities, the most recently pushed first.
+    stack: Vec<(InputContextId, InputContext<T>)>,
+}
+
+impl<T: InputAction> InputContexts<T> {
+    pub fn push(&mut self, context: InputContext<T>) -> InputContextId {
+        let id = InputContextId(self.next_id);
+        self.next_id += 1;
+
+        let index = self.stack.partition_point(|(_, other)| other.priority > context.priority);
+        self.stack.insert(index, (id, context));
+        id
+    }
+
+    /// Removes the most recently pushed context.
+    pub fn pop(&mut self) -> Option<InputContext<T>> {
+        let (index, _) = self.stack.iter().enumerate().max_by_key(|&(_, &(id, _))| id)?;
+        Some(self.stack.remove(index).1)
+    }
+
+    pub fn remove(&mut self, id: InputContextId) -> Option<InputContext<T>> {
+        let index = self.stack.iter().position(|&(other, _)| other == id)?;
+        Some(self.stack.remove(index).1)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.stack.clear();
+    }
+
+    #[inline]
+    pub fn contains(&self, id: InputContextId) -> bool {
+        self.stack.iter().any(|&(other, _)| other == id)
+    }
+
+    #[inline]
+    pub fn get(&self, id: InputContextId) -> Option<&InputContext<T>> {
+        self.stack.iter().find(|&&(other, _)| other == id).map(|(_, context)| context)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, id: InputContextId) -> Option<&mut InputContext<T>> {
+        self.stack.iter_mut().find(|(other, _)| *other == id).map(|(_, context)| context)
+    }
+
+    /// Moves the context within the stack, as if it was pushed again with the new priority.
+    pub fn set_priority(&mut self, id: InputContextId, priority: i32) {
+        let Some(mut context) = self.remove(id) else { return };
+        context.priority = priority;
+
+        let index = self.stack.partition_point(|(_, other)| other.priority > priority);
+        self.stack.insert(index, (id, context));
+    }
+
+    /// Iterates from the highest priority down.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (InputContextId, &InputContext<T>)> {
+        self.stack.iter().map(|(id, context)| (*id, context))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+}
+
+impl<T: InputAction> Default for InputContexts<T> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            stack: Vec::new(),
+        }
+    }
+}
+
+/// Inputs consumed by the contexts evaluated so far.
+#[derive(Default)]
+pub(crate) struct Consumed {
+    all: bool,
+    keyboard: bool,
+    buttons: HashSet<InputButton>,
+    analogs: HashSet<InputAnalog>,
+}
+
+impl Consumed {
+    pub fn add<T: InputAction>(&mut self, context: &InputContext<T>) {
+        match context.consume {
+            InputConsume::None => return,
+            InputConsume::Bound => {},
+            InputConsume::Keyboard => self.keyboard = true,
+            InputConsume::All => self.all = true,
+        }
+
+        for binding in context.bindings.map.values() {
+            self.buttons.extend(binding.buttons());
+            self.analogs.extend(binding.analogs());
+        }
+    }
+
+    #[inline]
+    pub fn button(&self, button: InputButton) -> bool {
+        self.all ||
+        (self.keyboard && matches!(button, InputButton::Key(_) | InputButton::Physical(_))) ||
+        self.buttons.contains(&button)
+    }
+
+    #[inline]
+    pub fn analog(&self, input: InputAnalog) -> bool {
+        self.all || self.analogs.contains(&input)
+    }
+}
@@ -1,9 +1,45 @@
-use crate::input::KeyCode;
+use crate::input::{
+    KeyCode,
+    MouseButton, MouseWheelUnit,
+    GamepadId, GamepadButton, GamepadAxis,
+};
+use bevy_math::Vec2;
+use derive_more::*;
+use serde::{
+    Deserialize, Serialize,
+};
 
 pub struct KeyEvent {
     /// `true` if pressed, `false` if released.
     pub pressed: bool,
-    pub key: KeyCode,
+    /// Whether this is a repeated press from holding the key down, as opposed to the initial one.
+    pub repeat: bool,
+    /// The key as mapped by the keyboard layout, if known.
+    pub key: Option<KeyCode>,
+    /// The key at this position on a US QWERTY layout, if known.
+    pub physical: Option<KeyCode>,
+    /// Platform-specific scancode of the key.
+    pub scancode: u32,
+}
+
+/// Text typed by the user, to be read by text fields rather than [`KeyEvent`]s, as it accounts for the keyboard layout,
+/// dead keys and the IME.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextInputEvent {
+    /// A typed character, repeated while its key is held. Control characters such as backspace or enter are left
+    /// out; read them from [`KeyEvent`]s.
+    Char(char),
+    /// The IME started composing; see [`Ime`](crate::input::Ime) to allow it.
+    ImeEnabled,
+    /// Text being composed by the IME, replacing the previous one; empty once cleared. `cursor` is the byte range
+    /// of the cursor or selection within it, if shown.
+    Preedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+    /// Text composed by the IME, to be inserted in place of the preedit.
+    Commit(String),
+    ImeDisabled,
 }
 
 pub struct KeyModifierEvent {
@@ -12,3 +48,80 @@ pub struct KeyModifierEvent {
     pub logo: bool,
     pub shift: bool,
 }
+
+pub struct MouseButtonEvent {
+    /// `true` if pressed, `false` if released.
+    pub pressed: bool,
+    pub button: MouseButton,
+}
+
+/// Cursor position in physical pixels from the top-left corner of the window.
+#[derive(Deref, DerefMut)]
+pub struct CursorMovedEvent(pub Vec2);
+pub struct CursorEnteredEvent;
+pub struct CursorLeftEvent;
+
+/// Raw mouse motion, unaffected by cursor acceleration or the window's bounds.
+#[derive(Deref, DerefMut)]
+pub struct MouseMotionEvent(pub Vec2);
+
+pub struct MouseWheelEvent {
+    /// Positive `y` scrolls up, positive `x` scrolls right.
+    pub delta: Vec2,
+    pub unit: MouseWheelUnit,
+}
+
+impl MouseWheelEvent {
+    /// The delta in lines, converting pixels with [`MouseWheelUnit::PIXELS_PER_LINE`].
+    #[inline]
+    pub fn lines(&self) -> Vec2 {
+        match self.unit {
+            MouseWheelUnit::Line => self.delta,
+            MouseWheelUnit::Pixel => self.delta / MouseWheelUnit::PIXELS_PER_LINE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GamepadEvent {
+    Connected {
+        gamepad: GamepadId,
+        name: String,
+    },
+    Disconnected {
+        gamepad: GamepadId,
+    },
+    Button {
+        gamepad: GamepadId,
+        button: GamepadButton,
+        /// `true` if pressed, `false` if released.
+        pressed: bool,
+    },
+    Axis {
+        gamepad: GamepadId,
+        axis: GamepadAxis,
+        value: f32,
+    },
+}
+
+impl GamepadEvent {
+    #[inline]
+    pub fn gamepad(&self) -> GamepadId {
+        match *self {
+            Self::Connected { gamepad, .. } |
+            Self::Disconnected { gamepad } |
+            Self::Button { gamepad, .. } |
+            Self::Axis { gamepad, .. } => gamepad,
+        }
+    }
+
+    #[inline]
+    pub fn gamepad_mut(&mut self) -> &mut GamepadId {
+        match self {
+            Self::Connected { gamepad, .. } |
+            Self::Disconnected { gamepad } |
+            Self::Button { gamepad, .. } |
+            Self::Axis { gamepad, .. } => gamepad,
+        }
+    }
+}
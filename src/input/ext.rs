@@ -0,0 +1,25 @@
+use crate::{
+    core::prelude::*,
+    input::{
+        GamepadSource, GamepadSources,
+        Clipboard, ClipboardBackend,
+    },
+};
+
+pub trait AppExt {
+    fn gamepad_source(&mut self, source: impl GamepadSource) -> &mut Self;
+
+    /// Replaces the [`Clipboard`]'s backend.
+    fn clipboard(&mut self, backend: impl ClipboardBackend) -> &mut Self;
+}
+
+impl AppExt for App {
+    fn gamepad_source(&mut self, source: impl GamepadSource) -> &mut Self {
+        self.res_ns_or(GamepadSources::default).add(source);
+        self
+    }
+
+    fn clipboard(&mut self, backend: impl ClipboardBackend) -> &mut Self {
+        self.insert_res_ns(Clipboard::new(backend))
+    }
+}
@@ -0,0 +1,323 @@
+use crate::{
+    core::prelude::*,
+    input::GamepadEvent,
+};
+use bevy_utils::{
+    default,
+    HashMap, HashSet,
+};
+use parking_lot::Mutex;
+use serde::{
+    Deserialize, Serialize,
+};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+};
+
+/// Identifies a connected gamepad. IDs are reused once their gamepad disconnects, lowest first.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GamepadId(pub usize);
+
+/// Gamepad buttons, named after their position on a standard controller.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadButton {
+    /// Bottom face button, e.g. A on Xbox or Cross on PlayStation controllers.
+    South,
+    East,
+    North,
+    West,
+    C,
+    Z,
+
+    LeftBumper,
+    RightBumper,
+    /// Digital state of the left trigger; see [`GamepadAxis::LeftTrigger`] for its analog value.
+    LeftTrigger,
+    RightTrigger,
+
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+
+    Other(u32),
+}
+
+/// Gamepad axes. Sticks range from `-1` to `1` with positive `y` upwards, triggers from `0` to `1`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftZ,
+    RightZ,
+    LeftTrigger,
+    RightTrigger,
+    DPadX,
+    DPadY,
+
+    Other(u32),
+}
+
+/// A backend reporting gamepad events, e.g. [`GilrsSource`](crate::input::GilrsSource) or [`VirtualGamepads`].
+/// Added with [`AppExt::gamepad_source`](crate::input::AppExt::gamepad_source).
+pub trait GamepadSource: 'static {
+    /// Pushes every event since the last poll. Gamepad IDs only need to be unique within this source, and every
+    /// gamepad must report [`GamepadEvent::Connected`] before anything else.
+    fn poll(&mut self, events: &mut Vec<GamepadEvent>);
+}
+
+/// Every registered [`GamepadSource`], polled at the start of each frame. Non-send, as some backends are.
+#[derive(Default)]
+pub struct GamepadSources {
+    sources: Vec<Box<dyn GamepadSource>>,
+    ids: HashMap<(usize, GamepadId), GamepadId>,
+    paused: bool,
+    buffer: Vec<GamepadEvent>,
+}
+
+impl GamepadSources {
+    pub fn poll_sys(mut sources: NonSendMut<Self>, mut events: EventWriter<GamepadEvent>) {
+        let sources = &mut *sources;
+        if sources.paused {
+            return;
+        }
+
+        for (index, source) in sources.sources.iter_mut().enumerate() {
+            source.poll(&mut sources.buffer);
+            for mut event in sources.buffer.drain(..) {
+                let key = (index, event.gamepad());
+                let id = match event {
+                    GamepadEvent::Connected { .. } => {
+                        let id = (0..).map(GamepadId).find(|id| !sources.ids.values().any(|used| used == id)).unwrap();
+                        sources.ids.insert(key, id);
+                        id
+                    },
+                    GamepadEvent::Disconnected { .. } => {
+                        let Some(id) = sources.ids.remove(&key) else { continue };
+                        id
+                    },
+                    _ => {
+                        let Some(&id) = sources.ids.get(&key) else { continue };
+                        id
+                    },
+                };
+
+                *event.gamepad_mut() = id;
+                events.send(event);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn add(&mut self, source: impl GamepadSource) {
+        self.sources.push(Box::new(source));
+    }
+
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stops polling the sources, e.g. while a replay feeds recorded gamepad events instead.
+    #[inline]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+}
+
+/// State of a single connected gamepad.
+#[derive(Debug, Default)]
+pub struct Gamepad {
+    name: String,
+    down: HashSet<GamepadButton>,
+    tapped: HashSet<GamepadButton>,
+    axes: HashMap<GamepadAxis, f32>,
+}
+
+impl Gamepad {
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn pressed(&self, button: GamepadButton) -> bool {
+        self.down.contains(&button)
+    }
+
+    /// Whether the button was pressed this frame.
+    #[inline]
+    pub fn tapped(&self, button: GamepadButton) -> bool {
+        self.tapped.contains(&button)
+    }
+
+    #[inline]
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0.)
+    }
+
+    /// Every held button.
+    #[inline]
+    pub fn buttons(&self) -> impl Iterator<Item = GamepadButton> + '_ {
+        self.down.iter().copied()
+    }
+
+    /// Every button pressed this frame.
+    #[inline]
+    pub fn tapped_buttons(&self) -> impl Iterator<Item = GamepadButton> + '_ {
+        self.tapped.iter().copied()
+    }
+
+    /// Every axis reported so far, along with its value.
+    #[inline]
+    pub fn axes(&self) -> impl Iterator<Item = (GamepadAxis, f32)> + '_ {
+        self.axes.iter().map(|(&axis, &value)| (axis, value))
+    }
+}
+
+/// Every connected gamepad, accumulated from [`GamepadEvent`]s every frame.
+#[derive(Resource, Debug, Default)]
+pub struct Gamepads {
+    pads: BTreeMap<GamepadId, Gamepad>,
+}
+
+impl Gamepads {
+    pub fn update_sys(mut gamepads: ResMut<Self>, mut events: EventReader<GamepadEvent>) {
+        for pad in gamepads.pads.values_mut() {
+            pad.tapped.clear();
+        }
+
+        for event in events.iter() {
+            match *event {
+                GamepadEvent::Connected { gamepad, ref name } => {
+                    log::info!("Gamepad {} connected: {}", gamepad.0, name);
+                    gamepads.pads.insert(gamepad, Gamepad {
+                        name: name.clone(),
+                        ..default()
+                    });
+                },
+                GamepadEvent::Disconnected { gamepad } => {
+                    log::info!("Gamepad {} disconnected", gamepad.0);
+                    gamepads.pads.remove(&gamepad);
+                },
+                GamepadEvent::Button { gamepad, button, pressed } => {
+                    let Some(pad) = gamepads.pads.get_mut(&gamepad) else { continue };
+                    if pressed {
+                        if pad.down.insert(button) {
+                            pad.tapped.insert(button);
+                        }
+                    } else {
+                        pad.down.remove(&button);
+                    }
+                },
+                GamepadEvent::Axis { gamepad, axis, value } => {
+                    let Some(pad) = gamepads.pads.get_mut(&gamepad) else { continue };
+                    pad.axes.insert(axis, value);
+                },
+            }
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, gamepad: GamepadId) -> Option<&Gamepad> {
+        self.pads.get(&gamepad)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (GamepadId, &Gamepad)> {
+        self.pads.iter().map(|(&id, pad)| (id, pad))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pads.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pads.is_empty()
+    }
+
+    /// Whether any gamepad holds the button.
+    #[inline]
+    pub fn pressed(&self, button: GamepadButton) -> bool {
+        self.pads.values().any(|pad| pad.pressed(button))
+    }
+
+    /// The axis value furthest from rest among every gamepad.
+    #[inline]
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.pads.values()
+            .map(|pad| pad.axis(axis))
+            .fold(0., |acc, value| if value.abs() > acc.abs() { value } else { acc })
+    }
+}
+
+/// A [`GamepadSource`] driven by code instead of hardware, e.g. to script input in tests. Clones share the same
+/// queue, so keep one around after adding it.
+#[derive(Default, Clone)]
+pub struct VirtualGamepads {
+    queue: Arc<Mutex<Vec<GamepadEvent>>>,
+    next: Arc<Mutex<usize>>,
+}
+
+impl VirtualGamepads {
+    pub fn connect(&self, name: impl Into<String>) -> VirtualGamepad {
+        let id = {
+            let mut next = self.next.lock();
+            *next += 1;
+            GamepadId(*next - 1)
+        };
+
+        self.queue.lock().push(GamepadEvent::Connected { gamepad: id, name: name.into() });
+        VirtualGamepad {
+            id,
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl GamepadSource for VirtualGamepads {
+    #[inline]
+    fn poll(&mut self, events: &mut Vec<GamepadEvent>) {
+        events.append(&mut self.queue.lock());
+    }
+}
+
+/// A gamepad connected through [`VirtualGamepads`]. Disconnects when dropped.
+pub struct VirtualGamepad {
+    id: GamepadId,
+    queue: Arc<Mutex<Vec<GamepadEvent>>>,
+}
+
+impl VirtualGamepad {
+    #[inline]
+    pub fn press(&self, button: GamepadButton) {
+        self.queue.lock().push(GamepadEvent::Button { gamepad: self.id, button, pressed: true });
+    }
+
+    #[inline]
+    pub fn release(&self, button: GamepadButton) {
+        self.queue.lock().push(GamepadEvent::Button { gamepad: self.id, button, pressed: false });
+    }
+
+    #[inline]
+    pub fn set_axis(&self, axis: GamepadAxis, value: f32) {
+        self.queue.lock().push(GamepadEvent::Axis { gamepad: self.id, axis, value });
+    }
+}
+
+impl Drop for VirtualGamepad {
+    fn drop(&mut self) {
+        self.queue.lock().push(GamepadEvent::Disconnected { gamepad: self.id });
+    }
+}
@@ -0,0 +1,111 @@
+use crate::input::{
+    GamepadEvent, GamepadId, GamepadButton, GamepadAxis, GamepadSource,
+};
+use gilrs::{
+    Axis, Button, Event, EventType, Gilrs,
+    ev::Code,
+};
+
+/// The native [`GamepadSource`], backed by [gilrs](https://gitlab.com/gilrs-project/gilrs).
+pub struct GilrsSource {
+    gilrs: Gilrs,
+    started: bool,
+}
+
+impl GilrsSource {
+    pub fn new() -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            gilrs: Gilrs::new().map_err(|err| anyhow::anyhow!("{}", err))?,
+            started: false,
+        })
+    }
+
+    fn button(button: Button, code: Code) -> GamepadButton {
+        match button {
+            Button::South => GamepadButton::South,
+            Button::East => GamepadButton::East,
+            Button::North => GamepadButton::North,
+            Button::West => GamepadButton::West,
+            Button::C => GamepadButton::C,
+            Button::Z => GamepadButton::Z,
+            Button::LeftTrigger => GamepadButton::LeftBumper,
+            Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+            Button::RightTrigger => GamepadButton::RightBumper,
+            Button::RightTrigger2 => GamepadButton::RightTrigger,
+            Button::Select => GamepadButton::Select,
+            Button::Start => GamepadButton::Start,
+            Button::Mode => GamepadButton::Mode,
+            Button::LeftThumb => GamepadButton::LeftThumb,
+            Button::RightThumb => GamepadButton::RightThumb,
+            Button::DPadUp => GamepadButton::DPadUp,
+            Button::DPadDown => GamepadButton::DPadDown,
+            Button::DPadLeft => GamepadButton::DPadLeft,
+            Button::DPadRight => GamepadButton::DPadRight,
+            Button::Unknown => GamepadButton::Other(code.into_u32()),
+        }
+    }
+
+    fn axis(axis: Axis, code: Code) -> GamepadAxis {
+        match axis {
+            Axis::LeftStickX => GamepadAxis::LeftStickX,
+            Axis::LeftStickY => GamepadAxis::LeftStickY,
+            Axis::LeftZ => GamepadAxis::LeftZ,
+            Axis::RightStickX => GamepadAxis::RightStickX,
+            Axis::RightStickY => GamepadAxis::RightStickY,
+            Axis::RightZ => GamepadAxis::RightZ,
+            Axis::DPadX => GamepadAxis::DPadX,
+            Axis::DPadY => GamepadAxis::DPadY,
+            Axis::Unknown => GamepadAxis::Other(code.into_u32()),
+        }
+    }
+}
+
+impl GamepadSource for GilrsSource {
+    fn poll(&mut self, events: &mut Vec<GamepadEvent>) {
+        // gilrs doesn't report gamepads that were connected before it started.
+        if !self.started {
+            self.started = true;
+            events.extend(self.gilrs.gamepads().map(|(id, pad)| GamepadEvent::Connected {
+                gamepad: GamepadId(id.into()),
+                name: pad.name().into(),
+            }));
+        }
+
+        while let Some(Event { id, event, .. }) = self.gilrs.next_event() {
+            let gamepad = GamepadId(id.into());
+            events.push(match event {
+                EventType::Connected => GamepadEvent::Connected {
+                    gamepad,
+                    name: self.gilrs.gamepad(id).name().into(),
+                },
+                EventType::Disconnected => GamepadEvent::Disconnected { gamepad },
+                EventType::ButtonPressed(button, code) => GamepadEvent::Button {
+                    gamepad,
+                    button: Self::button(button, code),
+                    pressed: true,
+                },
+                EventType::ButtonReleased(button, code) => GamepadEvent::Button {
+                    gamepad,
+                    button: Self::button(button, code),
+                    pressed: false,
+                },
+                EventType::ButtonChanged(Button::LeftTrigger2, value, _) => GamepadEvent::Axis {
+                    gamepad,
+                    axis: GamepadAxis::LeftTrigger,
+                    value,
+                },
+                EventType::ButtonChanged(Button::RightTrigger2, value, _) => GamepadEvent::Axis {
+                    gamepad,
+                    axis: GamepadAxis::RightTrigger,
+                    value,
+                },
+                EventType::AxisChanged(axis, value, code) => GamepadEvent::Axis {
+                    gamepad,
+                    axis: Self::axis(axis, code),
+                    value,
+                },
+                _ => continue,
+            });
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::input::{
+    InputAction, InputValue, InputBindings,
+};
+use bevy_utils::HashMap;
+use smallvec::SmallVec;
+use std::time::Duration;
+
+/// Actions tapped in order, each within `timeout` of the previous one, e.g. a fighting game's quarter-circle
+/// motion. Steps can be any action with a single value, including chords for diagonals. Tapping another step of the
+/// sequence out of order restarts it.
+#[derive(Debug, Clone)]
+pub struct InputSequence<T: InputAction> {
+    pub steps: SmallVec<[T; 4]>,
+    pub timeout: Duration,
+}
+
+impl<T: InputAction> InputSequence<T> {
+    #[inline]
+    pub fn new(steps: impl IntoIterator<Item = T>, timeout: Duration) -> Self {
+        Self {
+            steps: steps.into_iter().collect(),
+            timeout,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ActionTrack {
+    pressed: bool,
+    since: Duration,
+    last_tap: Duration,
+    taps: u32,
+}
+
+#[derive(Debug, Default)]
+struct SequenceTrack {
+    next: usize,
+    last: Duration,
+}
+
+/// Tracks taps, holds and sequences of every action over time.
+#[derive(Debug)]
+pub(crate) struct Interactions<T: InputAction> {
+    actions: HashMap<T, ActionTrack>,
+    sequences: HashMap<T, SequenceTrack>,
+}
+
+impl<T: InputAction> Default for Interactions<T> {
+    fn default() -> Self {
+        Self {
+            actions: HashMap::default(),
+            sequences: HashMap::default(),
+        }
+    }
+}
+
+impl<T: InputAction> Interactions<T> {
+    pub fn hold(&mut self, act: T) {
+        self.actions.entry(act).or_default().pressed = true;
+    }
+
+    pub fn retain(&mut self, bindings: &InputBindings<T>) {
+        self.actions.retain(|act, _| bindings.map.contains_key(act));
+        self.sequences.retain(|act, _| bindings.sequences.contains_key(act));
+    }
+
+    /// Fills in the interactions of every single value, `now` being the time since startup. Returns whether any
+    /// value changed.
+    pub fn update(&mut self, values: &mut HashMap<T, InputValue>, bindings: &InputBindings<T>, now: Duration) -> bool {
+        let mut changed = false;
+        for (act, value) in values.iter_mut() {
+            if bindings.sequences.contains_key(act) {
+                continue;
+            }
+
+            let InputValue::Single { pressed, tapped, released, held, taps } = value else { continue };
+            let track = self.actions.entry(*act).or_default();
+            let old = (*tapped, *released, *held, *taps);
+
+            *tapped = *pressed && !track.pressed;
+            *released = !*pressed && track.pressed;
+            track.pressed = *pressed;
+
+            if *tapped {
+                track.taps = if track.taps > 0 && now.saturating_sub(track.last_tap) <= bindings.tap_window {
+                    track.taps + 1
+                } else {
+                    1
+                };
+
+                track.last_tap = now;
+                track.since = now;
+            }
+
+            *taps = track.taps;
+            *held = if *pressed || *released {
+                now.saturating_sub(track.since)
+            } else {
+                Duration::ZERO
+            };
+
+            changed |= old != (*tapped, *released, *held, *taps);
+        }
+
+        for (act, sequence) in &bindings.sequences {
+            let track = self.sequences.entry(*act).or_default();
+            if track.next > 0 && now.saturating_sub(track.last) > sequence.timeout {
+                track.next = 0;
+            }
+
+            let is_tapped = |step: &T| values.get(step).and_then(|val| val.tapped()).unwrap_or(false);
+            let mut fired = false;
+            if sequence.steps.get(track.next).map_or(false, is_tapped) {
+                track.next += 1;
+                track.last = now;
+
+                if track.next == sequence.steps.len() {
+                    track.next = 0;
+                    fired = true;
+                }
+            } else if sequence.steps.iter().any(is_tapped) {
+                track.next = if sequence.steps.first().map_or(false, is_tapped) { 1 } else { 0 };
+                track.last = now;
+            }
+
+            let value = InputValue::Single {
+                pressed: fired,
+                tapped: fired,
+                released: false,
+                held: Duration::ZERO,
+                taps: fired as u32,
+            };
+
+            let was = values.insert(*act, value).and_then(InputValue::pressed).unwrap_or(false);
+            changed |= was || fired;
+        }
+
+        changed
+    }
+}
@@ -1,9 +1,18 @@
 use crate::{
     core::prelude::*,
-    input::KeyModifierEvent,
+    input::{
+        KeyModifierEvent,
+        MouseButton, GamepadButton,
+    },
 };
+use serde::{
+    Deserialize, Serialize,
+};
+use std::ops::BitOr;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+/// A logical key, i.e. the key the current keyboard layout maps to. See [`InputButton::Physical`] for
+/// layout-independent keys.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum KeyCode {
     N1, N2, N3, N4, N5,
     N6, N7, N8, N9, N0,
@@ -15,9 +24,44 @@ pub enum KeyCode {
 
     F1, F2, F3, F4, F5, F6,
     F7, F8, F9, F10, F11, F12,
+    F13, F14, F15, F16, F17, F18,
+    F19, F20, F21, F22, F23, F24,
+
+    Escape, Tab, CapsLock, Space, Enter, Backspace,
+    PrintScreen, ScrollLock, Pause,
+    Insert, Delete, Home, End, PageUp, PageDown,
+    Left, Up, Right, Down,
+
+    LShift, RShift,
+    LCtrl, RCtrl,
+    LAlt, RAlt,
+    LLogo, RLogo,
+    Menu, Compose,
+
+    NumLock,
+    Numpad0, Numpad1, Numpad2, Numpad3, Numpad4,
+    Numpad5, Numpad6, Numpad7, Numpad8, Numpad9,
+    NumpadAdd, NumpadSubtract, NumpadMultiply, NumpadDivide,
+    NumpadDecimal, NumpadComma, NumpadEnter, NumpadEquals,
+
+    Grave, Minus, Equals, LBracket, RBracket, Backslash,
+    Semicolon, Apostrophe, Comma, Period, Slash,
+    Asterisk, At, Caret, Colon, Plus, Underline,
+
+    AbntC1, AbntC2, Ax, Convert, Kana, Kanji, NoConvert, Oem102, Yen,
+
+    Mute, VolumeDown, VolumeUp,
+    PlayPause, MediaStop, NextTrack, PrevTrack, MediaSelect,
+
+    Power, Sleep, Wake, SysRq,
+    Calculator, Mail, MyComputer,
+    NavigateForward, NavigateBackward,
+    WebBack, WebForward, WebFavorites, WebHome, WebRefresh, WebSearch, WebStop,
+    Copy, Cut, Paste, Stop, Unlabeled,
 }
 
 impl KeyCode {
+    /// Digits of the top row; see [`is_numpad`](KeyCode::is_numpad) for the numeric keypad.
     #[inline]
     pub fn is_num(self) -> bool {
         use KeyCode::*;
@@ -45,10 +89,170 @@ impl KeyCode {
         use KeyCode::*;
         match self {
             F1 | F2 | F3 | F4 | F5 | F6 |
-            F7 | F8 | F9 | F10 | F11 | F12 => true,
+            F7 | F8 | F9 | F10 | F11 | F12 |
+            F13 | F14 | F15 | F16 | F17 | F18 |
+            F19 | F20 | F21 | F22 | F23 | F24 => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_arrow(self) -> bool {
+        use KeyCode::*;
+        matches!(self, Left | Up | Right | Down)
+    }
+
+    /// Arrows and the insert, delete, home, end, page up and page down cluster.
+    #[inline]
+    pub fn is_navigation(self) -> bool {
+        use KeyCode::*;
+        self.is_arrow() || matches!(self, Insert | Delete | Home | End | PageUp | PageDown)
+    }
+
+    /// Shift, control, alt and logo keys of either side.
+    #[inline]
+    pub fn is_modifier(self) -> bool {
+        use KeyCode::*;
+        match self {
+            LShift | RShift |
+            LCtrl | RCtrl |
+            LAlt | RAlt |
+            LLogo | RLogo => true,
             _ => false,
         }
     }
+
+    /// Every numeric keypad key, including its operators and num lock.
+    #[inline]
+    pub fn is_numpad(self) -> bool {
+        use KeyCode::*;
+        match self {
+            NumLock |
+            Numpad0 | Numpad1 | Numpad2 | Numpad3 | Numpad4 |
+            Numpad5 | Numpad6 | Numpad7 | Numpad8 | Numpad9 |
+            NumpadAdd | NumpadSubtract | NumpadMultiply | NumpadDivide |
+            NumpadDecimal | NumpadComma | NumpadEnter | NumpadEquals => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_punctuation(self) -> bool {
+        use KeyCode::*;
+        match self {
+            Grave | Minus | Equals | LBracket | RBracket | Backslash |
+            Semicolon | Apostrophe | Comma | Period | Slash |
+            Asterisk | At | Caret | Colon | Plus | Underline => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_whitespace(self) -> bool {
+        use KeyCode::*;
+        matches!(self, Space | Tab | Enter | NumpadEnter)
+    }
+
+    #[inline]
+    pub fn is_media(self) -> bool {
+        use KeyCode::*;
+        match self {
+            Mute | VolumeDown | VolumeUp |
+            PlayPause | MediaStop | NextTrack | PrevTrack | MediaSelect => true,
+            _ => false,
+        }
+    }
+
+    /// Keys specific to Japanese, Brazilian and other non-US layouts.
+    #[inline]
+    pub fn is_international(self) -> bool {
+        use KeyCode::*;
+        matches!(self, AbntC1 | AbntC2 | Ax | Convert | Kana | Kanji | NoConvert | Oem102 | Yen)
+    }
+}
+
+/// A key or button [`InputBinding`](crate::input::InputBinding)s react to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputButton {
+    /// A key as mapped by the keyboard layout, e.g. `Z` is where `Y` is on a German layout.
+    Key(KeyCode),
+    /// A key by its position, named after the key at that position on a US QWERTY layout. Use this for
+    /// layout-independent controls like WASD.
+    Physical(KeyCode),
+    Mouse(MouseButton),
+    /// A button of any connected gamepad.
+    Gamepad(GamepadButton),
+}
+
+impl From<KeyCode> for InputButton {
+    #[inline]
+    fn from(key: KeyCode) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<MouseButton> for InputButton {
+    #[inline]
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+
+impl From<GamepadButton> for InputButton {
+    #[inline]
+    fn from(button: GamepadButton) -> Self {
+        Self::Gamepad(button)
+    }
+}
+
+/// A set of modifier keys, each matching either side of the keyboard.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Modifiers {
+    pub alt: bool,
+    pub ctrl: bool,
+    pub logo: bool,
+    pub shift: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Self = Self { alt: false, ctrl: false, logo: false, shift: false };
+    pub const ALT: Self = Self { alt: true, ..Self::NONE };
+    pub const CTRL: Self = Self { ctrl: true, ..Self::NONE };
+    pub const LOGO: Self = Self { logo: true, ..Self::NONE };
+    pub const SHIFT: Self = Self { shift: true, ..Self::NONE };
+
+    /// Whether every modifier in `other` is also in `self`.
+    #[inline]
+    pub fn contains(self, other: Self) -> bool {
+        (self.alt || !other.alt) &&
+        (self.ctrl || !other.ctrl) &&
+        (self.logo || !other.logo) &&
+        (self.shift || !other.shift)
+    }
+
+    #[inline]
+    pub fn len(self) -> usize {
+        self.alt as usize + self.ctrl as usize + self.logo as usize + self.shift as usize
+    }
+
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self == Self::NONE
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        Self {
+            alt: self.alt || rhs.alt,
+            ctrl: self.ctrl || rhs.ctrl,
+            logo: self.logo || rhs.logo,
+            shift: self.shift || rhs.shift,
+        }
+    }
 }
 
 #[derive(Resource, Default)]
@@ -91,4 +295,14 @@ impl KeyModifier {
     pub fn shift(&self) -> bool {
         self.shift
     }
+
+    #[inline]
+    pub fn modifiers(&self) -> Modifiers {
+        Modifiers {
+            alt: self.alt,
+            ctrl: self.ctrl,
+            logo: self.logo,
+            shift: self.shift,
+        }
+    }
 }
@@ -1,100 +1,300 @@
 use crate::{
     core::prelude::*,
     input::{
-        KeyCode,
+        InputButton,
         InputAction, InputValue, InputState,
-        InputBindings,
-        KeyEvent,
+        InputBindings, InputAnalog, InputChord, RawInput, Interactions, InputContexts, InputContextId, Consumed,
+        KeyEvent, KeyModifier, MouseButtonEvent, MouseMotionEvent, MouseWheelEvent,
+        Gamepads, GamepadEvent, GamepadButton, GamepadId, InputPlayer,
     },
 };
+use bevy_ecs::system::SystemParam;
+use bevy_math::Vec2;
 use bevy_utils::{
     default,
     HashMap, HashSet,
 };
 use smallvec::SmallVec;
-use std::cell::Cell;
+use std::time::Duration;
+
+/// Every event and device state [`InputManager`] reads.
+#[derive(SystemParam)]
+pub struct InputEvents<'w, 's> {
+    keys: EventReader<'w, 's, KeyEvent>,
+    mouse_buttons: EventReader<'w, 's, MouseButtonEvent>,
+    mouse_motion: EventReader<'w, 's, MouseMotionEvent>,
+    mouse_wheel: EventReader<'w, 's, MouseWheelEvent>,
+    gamepads: EventReader<'w, 's, GamepadEvent>,
+    modifier: Res<'w, KeyModifier>,
+    gamepad_state: Res<'w, Gamepads>,
+}
 
 #[derive(Resource)]
 pub struct InputManager<T: InputAction> {
-    key_down: HashSet<KeyCode>,
-    key_tapped: HashSet<KeyCode>,
+    raw: RawInput,
 
-    key_changed: HashSet<KeyCode>,
+    key_changed: HashSet<InputButton>,
+    analog_changed: HashSet<InputAnalog>,
     act_changed: HashSet<T>,
 
-    key_assoc: HashMap<KeyCode, SmallVec<[T; 4]>>,
+    /// Gamepads holding each button, tracked from the events themselves so presses released within the same frame
+    /// still register.
+    gamepad_down: HashMap<GamepadButton, SmallVec<[GamepadId; 2]>>,
+    key_assoc: HashMap<InputButton, SmallVec<[T; 4]>>,
+    analog_assoc: HashMap<InputAnalog, SmallVec<[T; 4]>>,
+    chords: Vec<InputChord>,
+
+    base: Layer<T>,
+    layers: HashMap<InputContextId, Layer<T>>,
+    layered: bool,
+    consumed: Consumed,
+    players: HashMap<Entity, Layer<T>>,
     values: HashMap<T, InputValue>,
 }
 
 impl<T: InputAction> InputManager<T> {
     pub fn update_sys(
         mut manager: ResMut<Self>, bindings: Res<InputBindings<T>>,
-        mut key_events: EventReader<KeyEvent>,
-        mut query: Query<&mut InputState<T>>,
+        contexts: Res<InputContexts<T>>, mut events: InputEvents, time: Res<Time>,
+        mut query: Query<&mut InputState<T>, Without<InputPlayer>>,
     ) {
+        let manager = &mut *manager;
+        let raw = &mut manager.raw;
         let mut changed = false;
         let mut updated = false;
 
-        if !manager.key_tapped.is_empty() {
-            changed = true;
-            manager.key_tapped.clear();
-        }
-
+        // Buttons tapped last frame aren't anymore.
         manager.key_changed.clear();
-        for event in key_events.iter() {
-            manager.key_changed.insert(event.key);
-            if event.pressed {
-                if manager.key_down.insert(event.key) {
-                    manager.key_tapped.insert(event.key);
+        manager.key_changed.extend(raw.tapped.drain());
+
+        let gamepads = &*events.gamepad_state;
+        let mut press = |button: InputButton, pressed: bool| {
+            manager.key_changed.insert(button);
+            if pressed {
+                if raw.down.insert(button) {
+                    raw.tapped.insert(button);
                 }
             } else {
-                manager.key_down.remove(&event.key);
+                raw.down.remove(&button);
+            }
+        };
+
+        // Repeats don't change anything held.
+        for event in events.keys.iter().filter(|event| !event.repeat) {
+            if let Some(key) = event.key {
+                press(InputButton::Key(key), event.pressed);
+            }
+
+            if let Some(key) = event.physical {
+                press(InputButton::Physical(key), event.pressed);
+            }
+        }
+
+        for event in events.mouse_buttons.iter() {
+            press(InputButton::Mouse(event.button), event.pressed);
+        }
+
+        // Buttons are merged over every gamepad, held as long as any pad holds them. Axes are read back from
+        // `Gamepads` instead.
+        let gamepad_down = &mut manager.gamepad_down;
+        let mut gamepad_axes = HashSet::default();
+        let mut disconnected = false;
+        for event in events.gamepads.iter() {
+            match *event {
+                GamepadEvent::Button { gamepad, button, pressed } => {
+                    let pads = gamepad_down.entry(button).or_insert_with(default);
+                    if pressed {
+                        if !pads.contains(&gamepad) {
+                            pads.push(gamepad);
+                        }
+                    } else {
+                        pads.retain(|&mut pad| pad != gamepad);
+                    }
+
+                    press(InputButton::Gamepad(button), !pads.is_empty());
+                },
+                GamepadEvent::Axis { axis, .. } => {
+                    gamepad_axes.insert(axis);
+                },
+                GamepadEvent::Disconnected { gamepad } => {
+                    for (&button, pads) in gamepad_down.iter_mut() {
+                        if pads.contains(&gamepad) {
+                            pads.retain(|&mut pad| pad != gamepad);
+                            press(InputButton::Gamepad(button), !pads.is_empty());
+                        }
+                    }
+
+                    disconnected = true;
+                },
+                GamepadEvent::Connected { .. } => {},
+            }
+        }
+
+        if disconnected {
+            gamepad_axes.extend(raw.analog.keys().filter_map(|&input| match input {
+                InputAnalog::Gamepad(axis) => Some(axis),
+                _ => None,
+            }));
+        }
+
+        let motion = events.mouse_motion.iter().map(|event| event.0).sum::<Vec2>();
+        let wheel = events.mouse_wheel.iter().map(MouseWheelEvent::lines).sum::<Vec2>();
+
+        manager.analog_changed.clear();
+        for (input, value) in [
+            (InputAnalog::MouseMotionX, motion.x),
+            (InputAnalog::MouseMotionY, motion.y),
+            (InputAnalog::MouseWheelX, wheel.x),
+            (InputAnalog::MouseWheelY, wheel.y),
+        ].into_iter().chain(gamepad_axes.into_iter().map(|axis| (InputAnalog::Gamepad(axis), gamepads.axis(axis)))) {
+            if raw.analog.insert(input, value).unwrap_or(0.) != value {
+                manager.analog_changed.insert(input);
             }
         }
 
+        let modifiers = events.modifier.modifiers();
+        let modifiers_changed = raw.modifiers != modifiers;
+        raw.modifiers = modifiers;
+
+        changed |= !manager.key_changed.is_empty() || !manager.analog_changed.is_empty() || modifiers_changed;
+
+        if bindings.is_changed() {
+            manager.chords = bindings.chords().cloned().collect();
+        }
+
+        // Chords may take keys from any other binding, so re-evaluate everything when they're involved. Same once
+        // contexts aren't active anymore, as they were evaluated against their own copies.
+        if
+            bindings.is_changed() || manager.layered ||
+            (!manager.chords.is_empty() && (modifiers_changed || !manager.key_changed.is_empty()))
+        {
+            raw.claim(&manager.chords);
+            changed |= manager.layered;
+            updated = true;
+        }
+
         if bindings.is_changed() {
             changed = true;
             updated = true;
 
-            manager.values.drain_filter(|key, _| !bindings.map.contains_key(key));
+            manager.base.values.drain_filter(|key, _| !bindings.map.contains_key(key) && !bindings.sequences.contains_key(key));
+            manager.base.interactions.retain(&bindings);
             manager.key_assoc.clear();
+            manager.analog_assoc.clear();
 
             for (act, bind) in &bindings.map {
-                for key in bind.keys() {
+                for key in bind.buttons() {
                     manager.key_assoc.entry(key).or_insert_with(default).push(*act);
                 }
 
-                let value = bindings.map[act].value(&manager.key_down, &manager.key_tapped);
-                manager.values.insert(*act, value);
+                for analog in bind.analogs() {
+                    manager.analog_assoc.entry(analog).or_insert_with(default).push(*act);
+                }
             }
         }
 
-        if changed {
-            if !updated {
-                // Safety: There is guaranteed to be no data races whatsoever.
-                let act_changed = unsafe { &mut *(&mut manager.act_changed as *mut HashSet<T> as *mut Cell<HashSet<T>>) };
+        let now = time.elapsed_no_pause();
+        if !contexts.is_empty() || !manager.base.hidden.is_empty() {
+            // Contexts may consume anything, so evaluate every one of them from scratch, top-down.
+            let mut consumed = Consumed::default();
+            manager.layers.retain(|&id, _| contexts.contains(id));
+            manager.values.clear();
+
+            for (id, context) in contexts.iter() {
+                let layer = manager.layers.entry(id).or_insert_with(|| Layer {
+                    fresh: true,
+                    ..default()
+                });
+
+                layer.evaluate(&context.bindings, raw, &consumed, now);
+                layer.merge_into(&mut manager.values);
+                consumed.add(context);
+            }
+
+            manager.base.evaluate(&bindings, raw, &consumed, now);
+            manager.base.merge_into(&mut manager.values);
+            manager.layered = true;
+            manager.consumed = consumed;
+
+            for mut state in &mut query {
+                state.values = manager.values.clone();
+            }
+
+            return;
+        }
 
-                act_changed.get_mut().clear();
+        if manager.layered {
+            manager.layered = false;
+            manager.consumed = Consumed::default();
+        }
+
+        if changed {
+            if updated {
+                for (act, bind) in &bindings.map {
+                    manager.base.values.insert(*act, bind.value(raw, bindings.processor(act)));
+                }
+            } else {
+                manager.act_changed.clear();
                 for changed in &manager.key_changed {
                     let Some(assoc) = manager.key_assoc.get(changed) else { continue };
-                    for key in assoc {
-                        act_changed.get_mut().insert(*key);
-                    }
+                    manager.act_changed.extend(assoc.iter().copied());
                 }
 
-                for changed in act_changed.get_mut().iter() {
-                    let Some(bind) = bindings.map.get(changed) else { continue };
+                for changed in &manager.analog_changed {
+                    let Some(assoc) = manager.analog_assoc.get(changed) else { continue };
+                    manager.act_changed.extend(assoc.iter().copied());
+                }
 
-                    let value = bind.value(&manager.key_down, &manager.key_tapped);
-                    manager.values.insert(*changed, value);
+                for changed in &manager.act_changed {
+                    let Some(bind) = bindings.map.get(changed) else { continue };
+                    manager.base.values.insert(*changed, bind.value(raw, bindings.processor(changed)));
                 }
             }
+        }
 
+        // Taps, holds and sequences change over time even without new input.
+        changed |= manager.base.interactions.update(&mut manager.base.values, &bindings, now);
+        if changed {
+            manager.values.clone_from(&manager.base.values);
             for mut state in &mut query {
                 state.values = manager.values.clone();
             }
         }
     }
+
+    /// Evaluates the bindings of every [`InputPlayer`] against its own devices. Inputs consumed by active
+    /// [`InputContext`](crate::input::InputContext)s are hidden from players too.
+    pub fn player_sys(
+        mut manager: ResMut<Self>, bindings: Res<InputBindings<T>>, gamepads: Res<Gamepads>, time: Res<Time>,
+        mut query: Query<(Entity, &InputPlayer, Option<&InputBindings<T>>, &mut InputState<T>)>,
+    ) {
+        let manager = &mut *manager;
+        let now = time.elapsed_no_pause();
+
+        manager.players.retain(|&e, _| query.contains(e));
+        for (e, player, own_bindings, mut state) in &mut query {
+            let layer = manager.players.entry(e).or_insert_with(|| Layer {
+                fresh: true,
+                ..default()
+            });
+
+            let raw = manager.raw.gather(&player.devices, &gamepads);
+            layer.evaluate(own_bindings.unwrap_or(&bindings), &raw, &manager.consumed, now);
+            state.values.clone_from(&layer.values);
+        }
+    }
+
+    /// Keeps the action from being reported as tapped until it's released, e.g. after binding it to a held key.
+    #[inline]
+    pub fn hold(&mut self, act: T) {
+        self.base.interactions.hold(act);
+    }
+
+    /// Raw input state the bindings were last evaluated against.
+    #[inline]
+    pub fn raw(&self) -> &RawInput {
+        &self.raw
+    }
 }
 
 impl<T: InputAction> FromWorld for InputManager<T> {
@@ -104,14 +304,80 @@ impl<T: InputAction> FromWorld for InputManager<T> {
         }
 
         Self {
-            key_down: HashSet::default(),
-            key_tapped: HashSet::default(),
+            raw: RawInput::default(),
 
             key_changed: HashSet::default(),
+            analog_changed: HashSet::default(),
             act_changed: HashSet::default(),
 
+            gamepad_down: HashMap::default(),
             key_assoc: HashMap::default(),
+            analog_assoc: HashMap::default(),
+            chords: Vec::new(),
+
+            base: Layer::default(),
+            layers: HashMap::default(),
+            layered: false,
+            consumed: Consumed::default(),
+            players: HashMap::default(),
+            values: HashMap::default(),
+        }
+    }
+}
+
+/// Evaluation state of the base bindings or of a context.
+struct Layer<T: InputAction> {
+    interactions: Interactions<T>,
+    values: HashMap<T, InputValue>,
+    /// Buttons consumed by a higher context, hidden until released.
+    hidden: HashSet<InputButton>,
+    /// Whether the layer belongs to a context pushed since the last frame.
+    fresh: bool,
+}
+
+impl<T: InputAction> Layer<T> {
+    fn evaluate(&mut self, bindings: &InputBindings<T>, raw: &RawInput, consumed: &Consumed, now: Duration) {
+        self.hidden.retain(|&button| raw.pressed(button));
+        self.hidden.extend(raw.down.iter().copied().filter(|&button| consumed.button(button)));
+
+        let mut raw = raw.masked(&self.hidden, consumed);
+        raw.claim(bindings.chords());
+
+        self.values.clear();
+        for (act, bind) in &bindings.map {
+            self.values.insert(*act, bind.value(&raw, bindings.processor(act)));
+        }
+
+        self.interactions.retain(bindings);
+        if self.fresh {
+            // Anything already held was pressed before the context was pushed.
+            for (&act, value) in &self.values {
+                if value.pressed() == Some(true) {
+                    self.interactions.hold(act);
+                }
+            }
+
+            self.fresh = false;
+        }
+
+        self.interactions.update(&mut self.values, bindings, now);
+    }
+
+    /// Adds every value not already set by a higher layer.
+    fn merge_into(&self, values: &mut HashMap<T, InputValue>) {
+        for (&act, &value) in &self.values {
+            values.entry(act).or_insert(value);
+        }
+    }
+}
+
+impl<T: InputAction> Default for Layer<T> {
+    fn default() -> Self {
+        Self {
+            interactions: Interactions::default(),
             values: HashMap::default(),
+            hidden: HashSet::default(),
+            fresh: false,
         }
     }
 }
@@ -2,24 +2,64 @@ use crate::core::prelude::*;
 use std::marker::PhantomData;
 
 mod act;
+#[cfg(feature = "arboard")]
+mod arboard_clipboard;
 mod binding;
+mod context;
 mod event;
+mod ext;
+mod gamepad;
+#[cfg(feature = "gilrs")]
+mod gilrs_source;
+mod interaction;
 mod key;
 mod manager;
+mod mouse;
+mod player;
+mod process;
+mod profile;
+mod raw;
+mod rebind;
+mod text;
 
 pub use act::*;
+#[cfg(feature = "arboard")]
+pub use arboard_clipboard::*;
 pub use binding::*;
+pub use context::*;
 pub use event::*;
+pub use ext::*;
+pub use gamepad::*;
+#[cfg(feature = "gilrs")]
+pub use gilrs_source::*;
+pub use interaction::*;
 pub use key::*;
 pub use manager::*;
+pub use mouse::*;
+pub use player::*;
+pub use process::*;
+pub use profile::*;
+pub use raw::*;
+pub use rebind::*;
+pub use text::*;
 
 pub mod prelude {
     pub use crate::input::{
-        InputSubsystem,
-        KeyCode,
+        InputSubsystem, AppExt as _,
+        KeyCode, InputButton, Modifiers, KeyModifier,
         InputAction, InputValue, InputState,
-        InputBinding, InputLinear, InputAxis, InputBindings,
-        KeyEvent, KeyModifierEvent,
+        InputBinding, InputChord, InputSequence, InputLinear, InputAxis, InputAnalog, InputBindings,
+        InputProcessor, DeadZone, ResponseCurve,
+        InputProfile, InputRebinder, RebindEvent,
+        InputContextId, InputConsume, InputContext, InputContexts,
+        KeyEvent, KeyModifierEvent, TextInputEvent,
+        Ime, Clipboard, ClipboardBackend, MemoryClipboard,
+        MouseButton, MouseWheelUnit, Mouse,
+        MouseButtonEvent, MouseMotionEvent, MouseWheelEvent,
+        CursorMovedEvent, CursorEnteredEvent, CursorLeftEvent,
+        GamepadId, GamepadButton, GamepadAxis, Gamepad, Gamepads, GamepadEvent,
+        GamepadSource, VirtualGamepads, VirtualGamepad,
+        InputDevice, InputPlayer, InputJoin, PlayerJoinEvent,
         InputManager,
     };
 }
@@ -30,8 +70,86 @@ pub struct InputSubsystem<T: InputAction> {
 
 impl<T: InputAction> Subsystem for InputSubsystem<T> {
     fn init(app: &mut App) {
+        if !app.has_res::<KeyModifier>() {
+            app
+                .init_res::<KeyModifier>()
+                .sys(CoreStage::SysUpdate, KeyModifier::update_sys.label(CoreLabel::InputDevice));
+        }
+
+        if !app.has_res::<Mouse>() {
+            app
+                .init_res::<Mouse>()
+                .sys(CoreStage::SysUpdate, Mouse::update_sys.label(CoreLabel::InputDevice));
+        }
+
+        if !app.has_res::<Gamepads>() {
+            if !app.has_res::<GamepadSources>() {
+                app.insert_res_ns(GamepadSources::default());
+            }
+
+            #[cfg(feature = "gilrs")]
+            match GilrsSource::new() {
+                Ok(source) => {
+                    app.gamepad_source(source);
+                },
+                Err(err) => log::warn!("Native gamepads are unavailable: {}", err),
+            }
+
+            app
+                .init_res::<Gamepads>()
+                .sys(CoreStage::SysUpdate, GamepadSources::poll_sys.label(CoreLabel::GamepadPoll))
+                .sys(CoreStage::SysUpdate, Gamepads::update_sys
+                    .label(CoreLabel::InputDevice)
+                    .after(CoreLabel::GamepadPoll)
+                );
+        }
+
+        if !app.has_res::<Clipboard>() {
+            #[cfg(feature = "arboard")]
+            match ArboardClipboard::new() {
+                Ok(clipboard) => {
+                    app.clipboard(clipboard);
+                },
+                Err(err) => log::warn!("The system clipboard is unavailable: {}", err),
+            }
+
+            app.res_ns_or(Clipboard::default);
+        }
+
+        if !app.has_res::<InputJoin>() {
+            app
+                .init_res::<InputJoin>()
+                .event::<PlayerJoinEvent>()
+                .sys(CoreStage::SysUpdate, InputJoin::update_sys.after(CoreLabel::InputDevice));
+        }
+
         app
+            .event::<KeyEvent>()
+            .event::<KeyModifierEvent>()
+            .event::<TextInputEvent>()
+            .init_res::<Ime>()
+            .event::<MouseButtonEvent>()
+            .event::<MouseMotionEvent>()
+            .event::<MouseWheelEvent>()
+            .event::<CursorMovedEvent>()
+            .event::<CursorEnteredEvent>()
+            .event::<CursorLeftEvent>()
+            .event::<GamepadEvent>()
+            .event::<RebindEvent<T>>()
+            .init_res::<InputContexts<T>>()
             .init_res::<InputManager<T>>()
-            .sys(CoreStage::SysUpdate, InputManager::<T>::update_sys);
+            .init_res::<InputRebinder<T>>()
+            .sys(CoreStage::SysUpdate, InputManager::<T>::update_sys
+                .label(CoreLabel::InputUpdate)
+                .after(CoreLabel::InputDevice)
+                .after(CoreLabel::TimeUpdate)
+            )
+            .sys(CoreStage::SysUpdate, InputManager::<T>::player_sys.after(CoreLabel::InputUpdate))
+            .sys(CoreStage::SysUpdate, InputRebinder::<T>::update_sys.after(CoreLabel::InputUpdate))
+            .validate_requires_res::<InputState<T>, InputManager<T>>();
+    }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![SubsystemId::of::<CoreSubsystem>()]
     }
 }
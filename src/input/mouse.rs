@@ -0,0 +1,116 @@
+use crate::{
+    core::prelude::*,
+    input::{
+        CursorMovedEvent, CursorEnteredEvent, CursorLeftEvent, MouseButtonEvent, MouseMotionEvent, MouseWheelEvent,
+    },
+};
+use bevy_math::Vec2;
+use bevy_utils::HashSet;
+use serde::{
+    Deserialize, Serialize,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Other(u16),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MouseWheelUnit {
+    /// Lines or rows, reported by most mouse wheels.
+    Line,
+    /// Pixels, reported by touchpads and precision wheels.
+    Pixel,
+}
+
+impl MouseWheelUnit {
+    /// How many pixels make up a line when converting pixel deltas for [`InputAnalog`](crate::input::InputAnalog)s.
+    pub const PIXELS_PER_LINE: f32 = 20.;
+}
+
+/// Current mouse state, accumulated from mouse events every frame.
+#[derive(Resource, Debug, Default)]
+pub struct Mouse {
+    position: Option<Vec2>,
+    /// Whether the cursor last left the window, ignoring moves reported outside of it, e.g. while dragging.
+    outside: bool,
+    motion: Vec2,
+    wheel: Vec2,
+    down: HashSet<MouseButton>,
+    tapped: HashSet<MouseButton>,
+}
+
+impl Mouse {
+    pub fn update_sys(
+        mut mouse: ResMut<Self>,
+        mut cursor_events: EventReader<CursorMovedEvent>,
+        mut entered_events: EventReader<CursorEnteredEvent>,
+        mut left_events: EventReader<CursorLeftEvent>,
+        mut button_events: EventReader<MouseButtonEvent>,
+        mut motion_events: EventReader<MouseMotionEvent>,
+        mut wheel_events: EventReader<MouseWheelEvent>,
+    ) {
+        let mouse = &mut *mouse;
+
+        // Events of different types don't keep their relative order, but entering and leaving alternate, so whichever
+        // came more often this frame came last; e.g. leaving, entering then moving leaves the cursor inside.
+        let entered = entered_events.iter().count();
+        let left = left_events.iter().count();
+        if left != entered {
+            mouse.outside = left > entered;
+        }
+
+        let moved = cursor_events.iter().next_back();
+        if mouse.outside {
+            mouse.position = None;
+        } else if let Some(event) = moved {
+            mouse.position = Some(event.0);
+        }
+
+        mouse.tapped.clear();
+        for event in button_events.iter() {
+            if event.pressed {
+                if mouse.down.insert(event.button) {
+                    mouse.tapped.insert(event.button);
+                }
+            } else {
+                mouse.down.remove(&event.button);
+            }
+        }
+
+        mouse.motion = motion_events.iter().map(|event| event.0).sum();
+        mouse.wheel = wheel_events.iter().map(MouseWheelEvent::lines).sum();
+    }
+
+    /// Cursor position in physical pixels from the top-left corner of the window, or `None` if it's outside.
+    #[inline]
+    pub fn position(&self) -> Option<Vec2> {
+        self.position
+    }
+
+    /// Raw mouse motion this frame, unaffected by cursor acceleration or the window's bounds.
+    #[inline]
+    pub fn motion(&self) -> Vec2 {
+        self.motion
+    }
+
+    /// Wheel scroll this frame in lines, positive `y` scrolling up.
+    #[inline]
+    pub fn wheel(&self) -> Vec2 {
+        self.wheel
+    }
+
+    #[inline]
+    pub fn pressed(&self, button: MouseButton) -> bool {
+        self.down.contains(&button)
+    }
+
+    /// Whether the button was pressed this frame.
+    #[inline]
+    pub fn tapped(&self, button: MouseButton) -> bool {
+        self.tapped.contains(&button)
+    }
+}
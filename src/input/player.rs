@@ -0,0 +1,112 @@
+use crate::{
+    core::prelude::*,
+    input::{
+        KeyCode, InputButton, KeyEvent, MouseButtonEvent,
+        GamepadId, GamepadButton, Gamepads,
+    },
+};
+use bevy_utils::HashSet;
+use serde::{
+    Deserialize, Serialize,
+};
+use smallvec::{
+    smallvec, SmallVec,
+};
+
+/// An input device a player can be assigned.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputDevice {
+    /// The keyboard and mouse, or a share of them when several players use them at once, e.g. `Keyboard(0)` on WASD
+    /// and `Keyboard(1)` on the arrows. Shares differ only by the bindings of the players using them.
+    Keyboard(u8),
+    Gamepad(GamepadId),
+}
+
+/// Drives the entity's [`InputState`](crate::input::InputState)s from the assigned devices only, instead of every
+/// device at once. The entity's own [`InputBindings`](crate::input::InputBindings) component is used if it has one,
+/// the global resource otherwise.
+#[derive(Component, Debug, Clone, Default)]
+pub struct InputPlayer {
+    pub devices: SmallVec<[InputDevice; 2]>,
+}
+
+impl InputPlayer {
+    #[inline]
+    pub fn new(devices: impl IntoIterator<Item = InputDevice>) -> Self {
+        Self {
+            devices: devices.into_iter().collect(),
+        }
+    }
+
+    #[inline]
+    pub fn has(&self, device: InputDevice) -> bool {
+        self.devices.contains(&device)
+    }
+}
+
+/// Sent when a button is pressed on a device no [`InputPlayer`] has claimed yet, while joining is enabled. Spawning
+/// the player is left to the game.
+#[derive(Debug, Copy, Clone)]
+pub struct PlayerJoinEvent {
+    pub device: InputDevice,
+}
+
+/// "Press to join" for local multiplayer, sending [`PlayerJoinEvent`]s.
+#[derive(Resource, Debug, Clone)]
+pub struct InputJoin {
+    /// Off by default, e.g. turned on in a lobby.
+    pub enabled: bool,
+    /// Buttons that join with the gamepad they're pressed on; start and south by default.
+    pub gamepad_buttons: SmallVec<[GamepadButton; 2]>,
+    /// Keyboard or mouse buttons that join with a keyboard share, e.g. space for `Keyboard(0)` and enter for
+    /// `Keyboard(1)`. Enter joins with `Keyboard(0)` by default.
+    pub keyboard_buttons: SmallVec<[(InputButton, u8); 2]>,
+}
+
+impl Default for InputJoin {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            gamepad_buttons: smallvec![GamepadButton::Start, GamepadButton::South],
+            keyboard_buttons: smallvec![(InputButton::Key(KeyCode::Enter), 0)],
+        }
+    }
+}
+
+impl InputJoin {
+    pub fn update_sys(
+        join: Res<Self>, gamepads: Res<Gamepads>, players: Query<&InputPlayer>,
+        mut keys: EventReader<KeyEvent>, mut mouse_buttons: EventReader<MouseButtonEvent>,
+        mut events: EventWriter<PlayerJoinEvent>,
+    ) {
+        let mut pressed = SmallVec::<[InputButton; 4]>::new();
+        for event in keys.iter().filter(|event| event.pressed && !event.repeat) {
+            pressed.extend(event.key.map(InputButton::Key));
+            pressed.extend(event.physical.map(InputButton::Physical));
+        }
+
+        pressed.extend(mouse_buttons.iter().filter(|event| event.pressed).map(|event| InputButton::Mouse(event.button)));
+        if !join.enabled {
+            return;
+        }
+
+        let mut claimed = players.iter().flat_map(|player| player.devices.iter().copied()).collect::<HashSet<_>>();
+        let mut send = |device| {
+            if claimed.insert(device) {
+                events.send(PlayerJoinEvent { device });
+            }
+        };
+
+        for &(button, share) in &join.keyboard_buttons {
+            if pressed.contains(&button) {
+                send(InputDevice::Keyboard(share));
+            }
+        }
+
+        for (id, pad) in gamepads.iter() {
+            if pad.tapped_buttons().any(|button| join.gamepad_buttons.contains(&button)) {
+                send(InputDevice::Gamepad(id));
+            }
+        }
+    }
+}
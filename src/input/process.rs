@@ -0,0 +1,152 @@
+use bevy_math::{
+    BVec2, Vec2,
+};
+use serde::{
+    Deserialize, Serialize,
+};
+
+/// Ignores small analog values, e.g. from a worn stick that doesn't rest at exactly 0. Values past the threshold are
+/// rescaled so the output still starts at 0.
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum DeadZone {
+    #[default]
+    None,
+    /// Applies to each component separately, snapping the stick to the axes near them.
+    Axial(f32),
+    /// Applies to the vector's length, keeping its direction.
+    Radial(f32),
+}
+
+impl DeadZone {
+    #[inline]
+    pub fn apply_linear(self, value: f32) -> f32 {
+        match self {
+            Self::None => value,
+            Self::Axial(threshold) | Self::Radial(threshold) => Self::rescale(value.abs(), threshold) * value.signum(),
+        }
+    }
+
+    #[inline]
+    pub fn apply_axis(self, value: Vec2) -> Vec2 {
+        match self {
+            Self::None => value,
+            Self::Axial(_) => Vec2::new(self.apply_linear(value.x), self.apply_linear(value.y)),
+            Self::Radial(threshold) => {
+                let len = value.length();
+                if len == 0. {
+                    Vec2::ZERO
+                } else {
+                    value * (Self::rescale(len, threshold) / len)
+                }
+            },
+        }
+    }
+
+    #[inline]
+    fn rescale(len: f32, threshold: f32) -> f32 {
+        if len <= threshold || threshold >= 1. {
+            0.
+        } else {
+            (len - threshold) / (1. - threshold)
+        }
+    }
+}
+
+/// Maps an analog value's magnitude, keeping its sign or direction. Curves should map `0` to `0` and `1` to `1`.
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum ResponseCurve {
+    #[default]
+    Linear,
+    /// Finer control near rest, `x²`.
+    Quadratic,
+    /// Even finer control near rest, `x³`.
+    Cubic,
+    /// `x` to the given power.
+    Power(f32),
+    /// Can't be saved; processors using it are left out of [`InputProfile`](crate::input::InputProfile)s.
+    #[serde(skip)]
+    Custom(fn(f32) -> f32),
+}
+
+impl ResponseCurve {
+    #[inline]
+    pub fn apply(self, magnitude: f32) -> f32 {
+        match self {
+            Self::Linear => magnitude,
+            Self::Quadratic => magnitude * magnitude,
+            Self::Cubic => magnitude * magnitude * magnitude,
+            Self::Power(exp) => magnitude.powf(exp),
+            Self::Custom(curve) => curve(magnitude),
+        }
+    }
+
+    #[inline]
+    pub fn apply_linear(self, value: f32) -> f32 {
+        self.apply(value.abs()) * value.signum()
+    }
+
+    #[inline]
+    pub fn apply_axis(self, value: Vec2) -> Vec2 {
+        let len = value.length();
+        if len == 0. {
+            Vec2::ZERO
+        } else {
+            value * (self.apply(len) / len)
+        }
+    }
+}
+
+/// Post-processes the value of an action's binding before it reaches [`InputState`](crate::input::InputState).
+/// Dead zones and curves only apply to analog inputs; key presses are always full strength.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputProcessor {
+    pub dead_zone: DeadZone,
+    pub curve: ResponseCurve,
+    /// Flips the value; linear bindings only use `x`.
+    pub invert: BVec2,
+    /// Multiplies the value after the curve; linear bindings only use `x`.
+    pub sensitivity: Vec2,
+    /// Scales diagonal key presses to unit length so they aren't faster than a single direction.
+    pub normalize: bool,
+}
+
+impl Default for InputProcessor {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl InputProcessor {
+    /// Leaves analog values untouched and normalizes diagonal key presses.
+    pub const DEFAULT: Self = Self {
+        dead_zone: DeadZone::None,
+        curve: ResponseCurve::Linear,
+        invert: BVec2::FALSE,
+        sensitivity: Vec2::ONE,
+        normalize: true,
+    };
+
+    pub fn linear(&self, value: f32, analog: bool) -> f32 {
+        let value = if analog {
+            self.curve.apply_linear(self.dead_zone.apply_linear(value))
+        } else {
+            value
+        };
+
+        (if self.invert.x { -value } else { value }) * self.sensitivity.x
+    }
+
+    pub fn axis(&self, value: Vec2, analog: bool) -> Vec2 {
+        let value = if analog {
+            self.curve.apply_axis(self.dead_zone.apply_axis(value))
+        } else if self.normalize {
+            value.normalize_or_zero()
+        } else {
+            value
+        };
+
+        Vec2::select(self.invert, -value, value) * self.sensitivity
+    }
+}
@@ -0,0 +1,114 @@
+use crate::input::{
+    InputAction, InputBinding, InputBindings, InputProcessor, ResponseCurve,
+};
+use bevy_utils::HashMap;
+use serde::{
+    de::DeserializeOwned,
+    Deserialize, Serialize,
+};
+use std::{
+    fs, io,
+    path::Path,
+    time::Duration,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputProfileError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Syntax(#[from] ron::de::SpannedError),
+    #[error(transparent)]
+    Ron(#[from] ron::Error),
+}
+
+/// A user's customized bindings, saved to and loaded from a RON file. Actions missing from the profile keep their
+/// defaults when it's applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(serialize = "T: Serialize", deserialize = "T: DeserializeOwned"))]
+pub struct InputProfile<T: InputAction> {
+    pub bindings: HashMap<T, InputBinding>,
+    #[serde(default)]
+    pub processors: HashMap<T, InputProcessor>,
+    #[serde(default)]
+    pub tap_window: Option<Duration>,
+}
+
+impl<T: InputAction + Serialize> InputProfile<T> {
+    pub fn serialize_ron(&self) -> Result<String, InputProfileError> {
+        let config = ron::ser::PrettyConfig::default()
+            .indentor("    ".to_string())
+            .struct_names(false);
+
+        Ok(ron::ser::to_string_pretty(self, config)?)
+    }
+
+    /// Writes the profile, creating parent directories as needed.
+    pub fn save(&self, path: &Path) -> Result<(), InputProfileError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, self.serialize_ron()?)?;
+        Ok(())
+    }
+}
+
+impl<T: InputAction + DeserializeOwned> InputProfile<T> {
+    pub fn deserialize_ron(source: &str) -> Result<Self, InputProfileError> {
+        Ok(ron::de::from_str(source)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, InputProfileError> {
+        Self::deserialize_ron(&fs::read_to_string(path)?)
+    }
+}
+
+impl<T: InputAction> InputBindings<T> {
+    /// Snapshots the current bindings and processors. Actions bound to their default are left out, so changing a
+    /// default later still applies to users who never rebound them, while unbound actions that have a default are
+    /// written as empty bindings so they stay unbound once applied. Processors with a
+    /// [`ResponseCurve::Custom`] curve can't be serialized and are left out too; applying the profile keeps them.
+    pub fn to_profile(&self) -> InputProfile<T> {
+        InputProfile {
+            bindings: self.map
+                .iter()
+                .filter(|&(act, binding)| self.defaults.get(act) != Some(binding))
+                .map(|(&act, binding)| (act, binding.clone()))
+                .chain(self.defaults
+                    .iter()
+                    .filter(|&(act, _)| !self.map.contains_key(act))
+                    .map(|(&act, binding)| (act, binding.cleared()))
+                )
+                .collect(),
+            processors: self.processors
+                .iter()
+                .filter(|&(_, processor)| !matches!(processor.curve, ResponseCurve::Custom(_)))
+                .map(|(&act, &processor)| (act, processor))
+                .collect(),
+            tap_window: Some(self.tap_window),
+        }
+    }
+
+    /// Resets every binding to its default, then applies the profile over them.
+    pub fn apply_profile(&mut self, profile: InputProfile<T>) {
+        self.reset_all();
+        self.map.extend(profile.bindings);
+        self.processors.extend(profile.processors);
+        if let Some(window) = profile.tap_window {
+            self.tap_window = window;
+        }
+    }
+
+    #[inline]
+    pub fn save_profile(&self, path: &Path) -> Result<(), InputProfileError> where T: Serialize {
+        self.to_profile().save(path)
+    }
+
+    #[inline]
+    pub fn load_profile(&mut self, path: &Path) -> Result<(), InputProfileError> where T: DeserializeOwned {
+        self.apply_profile(InputProfile::load(path)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use crate::input::{
+    InputButton, InputAnalog, InputChord, Modifiers, Consumed,
+    InputDevice, Gamepads,
+};
+use bevy_utils::{
+    HashMap, HashSet,
+};
+
+/// Raw input state [`InputBinding`](crate::input::InputBinding)s are evaluated against, kept by
+/// [`InputManager`](crate::input::InputManager).
+#[derive(Debug, Default)]
+pub struct RawInput {
+    pub(crate) down: HashSet<InputButton>,
+    pub(crate) tapped: HashSet<InputButton>,
+    pub(crate) analog: HashMap<InputAnalog, f32>,
+    pub(crate) modifiers: Modifiers,
+    /// How specific the most specific active chord each button is part of is.
+    pub(crate) claimed: HashMap<InputButton, usize>,
+}
+
+impl RawInput {
+    /// Whether the button is held, or was pressed this frame even if it's already released, so a press and
+    /// release within a single frame still lasts a frame.
+    #[inline]
+    pub fn pressed(&self, button: InputButton) -> bool {
+        self.down.contains(&button) || self.tapped.contains(&button)
+    }
+
+    /// Whether the button was pressed this frame.
+    #[inline]
+    pub fn tapped(&self, button: InputButton) -> bool {
+        self.tapped.contains(&button)
+    }
+
+    #[inline]
+    pub fn analog(&self, input: InputAnalog) -> f32 {
+        self.analog.get(&input).copied().unwrap_or(0.)
+    }
+
+    #[inline]
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    /// Whether the button is held and isn't part of an active chord more specific than `specificity`, e.g. `S` is
+    /// taken while `Ctrl+S` is held and bound.
+    #[inline]
+    pub fn available(&self, button: InputButton, specificity: usize) -> bool {
+        self.pressed(button) && !matches!(self.claimed.get(&button), Some(&claimed) if claimed > specificity)
+    }
+
+    /// The input of the given devices only, as seen by an [`InputPlayer`](crate::input::InputPlayer). Gamepad
+    /// inputs are read from `gamepads` instead of `self`, as it merges them over every gamepad.
+    pub(crate) fn gather(&self, devices: &[InputDevice], gamepads: &Gamepads) -> Self {
+        let mut raw = Self::default();
+        for &device in devices {
+            match device {
+                InputDevice::Keyboard(_) => {
+                    let is_local = |button: &InputButton| !matches!(button, InputButton::Gamepad(_));
+                    raw.down.extend(self.down.iter().copied().filter(is_local));
+                    raw.tapped.extend(self.tapped.iter().copied().filter(is_local));
+                    raw.analog.extend(self.analog
+                        .iter()
+                        .filter(|&(input, _)| !matches!(input, InputAnalog::Gamepad(_)))
+                        .map(|(&input, &value)| (input, value))
+                    );
+
+                    raw.modifiers = self.modifiers;
+                },
+                InputDevice::Gamepad(gamepad) => {
+                    let Some(pad) = gamepads.get(gamepad) else { continue };
+                    raw.down.extend(pad.buttons().map(InputButton::Gamepad));
+                    raw.tapped.extend(pad.tapped_buttons().map(InputButton::Gamepad));
+
+                    for (axis, value) in pad.axes() {
+                        let current = raw.analog.entry(InputAnalog::Gamepad(axis)).or_insert(0.);
+                        if value.abs() > current.abs() {
+                            *current = value;
+                        }
+                    }
+                },
+            }
+        }
+
+        raw
+    }
+
+    /// A copy with the hidden buttons released and the consumed analog inputs at rest, as seen by a lower context.
+    pub(crate) fn masked(&self, hidden: &HashSet<InputButton>, consumed: &Consumed) -> Self {
+        Self {
+            down: self.down.difference(hidden).copied().collect(),
+            tapped: self.tapped.difference(hidden).copied().collect(),
+            analog: self.analog
+                .iter()
+                .filter(|&(&input, _)| !consumed.analog(input))
+                .map(|(&input, &value)| (input, value))
+                .collect(),
+            modifiers: self.modifiers,
+            claimed: HashMap::default(),
+        }
+    }
+
+    pub(crate) fn claim<'a>(&mut self, chords: impl IntoIterator<Item = &'a InputChord>) {
+        self.claimed.clear();
+        for chord in chords {
+            if !chord.is_held(self) {
+                continue;
+            }
+
+            let specificity = chord.specificity();
+            for &button in &chord.buttons {
+                let claimed = self.claimed.entry(button).or_insert(0);
+                *claimed = specificity.max(*claimed);
+            }
+        }
+    }
+}
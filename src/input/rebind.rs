@@ -0,0 +1,208 @@
+use crate::{
+    core::prelude::*,
+    input::{
+        InputAction, InputBinding, InputBindings, InputButton, InputChord, InputLinear, InputAxis, InputAnalog,
+        InputManager, KeyCode, GamepadAxis,
+    },
+};
+use smallvec::{smallvec, SmallVec};
+
+#[derive(Debug, Clone)]
+pub enum RebindEvent<T: InputAction> {
+    /// The action was bound to the captured input. `conflicts` lists every other action sharing an input with it,
+    /// which is left to the game to resolve.
+    Bound {
+        act: T,
+        binding: InputBinding,
+        conflicts: Vec<T>,
+    },
+    Cancelled {
+        act: T,
+    },
+}
+
+/// Listens for the next input to rebind an action to, e.g. from a controls menu. Single actions capture a button,
+/// with the held modifiers making it a chord. Linear and axis actions capture either a gamepad axis pushed past the
+/// threshold, or a sequence of buttons: positive then negative for linear actions, and up, down, left, then right
+/// for axis actions; see [`captured`](Self::captured) and [`remaining`](Self::remaining) to prompt for each.
+#[derive(Resource)]
+pub struct InputRebinder<T: InputAction> {
+    listening: Option<T>,
+    pending: Option<InputButton>,
+    captured: SmallVec<[InputButton; 4]>,
+
+    /// Stops listening without rebinding; escape by default.
+    pub cancel_button: Option<InputButton>,
+    /// Captures keys by their position instead of by the keyboard layout.
+    pub physical: bool,
+    /// How far a gamepad axis must be pushed to be captured.
+    pub threshold: f32,
+}
+
+impl<T: InputAction> Default for InputRebinder<T> {
+    fn default() -> Self {
+        Self {
+            listening: None,
+            pending: None,
+            captured: SmallVec::new(),
+
+            cancel_button: Some(InputButton::Key(KeyCode::Escape)),
+            physical: false,
+            threshold: 0.5,
+        }
+    }
+}
+
+impl<T: InputAction> InputRebinder<T> {
+    pub fn update_sys(
+        mut rebinder: ResMut<Self>, mut manager: ResMut<InputManager<T>>, mut bindings: ResMut<InputBindings<T>>,
+        mut events: EventWriter<RebindEvent<T>>,
+    ) {
+        let Some(act) = rebinder.listening else { return };
+        let raw = manager.raw();
+
+        if rebinder.cancel_button.map_or(false, |cancel| raw.tapped(cancel)) {
+            rebinder.cancel();
+            events.send(RebindEvent::Cancelled { act });
+            return;
+        }
+
+        let binding = match bindings.get(&act).or_else(|| bindings.default_binding(&act)) {
+            Some(InputBinding::Linear(_)) => rebinder
+                .capture_analog(&manager, false)
+                .or_else(|| rebinder.capture_keys(&manager, false)),
+            Some(InputBinding::Axis(_)) => rebinder
+                .capture_analog(&manager, true)
+                .or_else(|| rebinder.capture_keys(&manager, true)),
+            _ => rebinder.capture_button(&manager),
+        };
+
+        let Some(binding) = binding else { return };
+        let conflicts = bindings.conflicts(&act, &binding);
+        bindings.bind(act, binding.clone());
+
+        // The captured input is likely still held; don't let it trigger the action right away.
+        manager.hold(act);
+        rebinder.cancel();
+        events.send(RebindEvent::Bound { act, binding, conflicts });
+    }
+
+    /// Starts listening for the action's new binding, replacing any previous request.
+    #[inline]
+    pub fn listen(&mut self, act: T) {
+        self.listening = Some(act);
+        self.pending = None;
+        self.captured.clear();
+    }
+
+    #[inline]
+    pub fn cancel(&mut self) {
+        self.listening = None;
+        self.pending = None;
+        self.captured.clear();
+    }
+
+    #[inline]
+    pub fn listening(&self) -> Option<T> {
+        self.listening
+    }
+
+    /// Buttons captured so far for a linear or axis action, in binding order.
+    #[inline]
+    pub fn captured(&self) -> &[InputButton] {
+        &self.captured
+    }
+
+    /// How many buttons are still needed to bind the action being listened for, or `None` if it binds on a single
+    /// input.
+    pub fn remaining(&self, bindings: &InputBindings<T>) -> Option<usize> {
+        let act = self.listening?;
+        match bindings.get(&act).or_else(|| bindings.default_binding(&act))? {
+            InputBinding::Linear(_) => Some(2 - self.captured.len()),
+            InputBinding::Axis(_) => Some(4 - self.captured.len()),
+            _ => None,
+        }
+    }
+
+    fn tapped<'a>(&self, manager: &'a InputManager<T>) -> impl Iterator<Item = InputButton> + Clone + 'a {
+        let physical = self.physical;
+        manager.raw().tapped.iter().copied().filter(move |&button| match button {
+            InputButton::Key(_) => !physical,
+            InputButton::Physical(_) => physical,
+            _ => true,
+        })
+    }
+
+    fn capture_button(&mut self, manager: &InputManager<T>) -> Option<InputBinding> {
+        let raw = manager.raw();
+        let is_modifier = |button: InputButton| match button {
+            InputButton::Key(key) | InputButton::Physical(key) => key.is_modifier(),
+            _ => false,
+        };
+
+        let mut tapped = self.tapped(manager);
+
+        if let Some(button) = tapped.clone().find(|&button| !is_modifier(button)) {
+            let modifiers = raw.modifiers();
+            return Some(if modifiers.is_empty() {
+                InputBinding::Single(smallvec![button])
+            } else {
+                InputBinding::Chord(smallvec![InputChord::new(modifiers, [button])])
+            });
+        }
+
+        // Modifiers only bind on their own once released without another key in between.
+        if let Some(pending) = self.pending.filter(|&pending| !raw.pressed(pending)) {
+            return Some(InputBinding::Single(smallvec![pending]));
+        }
+
+        if let Some(modifier) = tapped.find(|&button| is_modifier(button)) {
+            self.pending = Some(modifier);
+        }
+
+        None
+    }
+
+    /// Captures one button a frame until there are enough for [`InputLinear::Keys`] or [`InputAxis::Keys`].
+    fn capture_keys(&mut self, manager: &InputManager<T>, axis: bool) -> Option<InputBinding> {
+        let captured = &self.captured;
+        let button = self.tapped(manager).find(|button| !captured.contains(button))?;
+        self.captured.push(button);
+
+        match (axis, &self.captured[..]) {
+            (false, &[positive, negative]) => {
+                Some(InputBinding::Linear(smallvec![InputLinear::new(positive, negative)]))
+            },
+            (true, &[up, down, left, right]) => {
+                Some(InputBinding::Axis(smallvec![InputAxis::keys(up, down, left, right)]))
+            },
+            _ => None,
+        }
+    }
+
+    fn capture_analog(&self, manager: &InputManager<T>, axis: bool) -> Option<InputBinding> {
+        // Once buttons are being captured, a stray stick push shouldn't discard them.
+        if !self.captured.is_empty() {
+            return None;
+        }
+
+        let raw = manager.raw();
+        let (input, _) = raw.analog
+            .iter()
+            .filter(|&(input, value)| matches!(input, InputAnalog::Gamepad(_)) && value.abs() >= self.threshold)
+            .max_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))?;
+
+        let InputAnalog::Gamepad(gamepad_axis) = *input else { return None };
+        Some(if axis {
+            use GamepadAxis::*;
+            InputBinding::Axis(smallvec![match gamepad_axis {
+                LeftStickX | LeftStickY => InputAxis::left_stick(),
+                RightStickX | RightStickY => InputAxis::right_stick(),
+                DPadX | DPadY => InputAxis::analog(DPadX, DPadY),
+                _ => return None,
+            }])
+        } else {
+            InputBinding::Linear(smallvec![InputLinear::analog(*input)])
+        })
+    }
+}
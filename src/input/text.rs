@@ -0,0 +1,71 @@
+use crate::core::prelude::*;
+use bevy_math::Vec2;
+
+/// Controls the platform's input method editor, which reports through
+/// [`TextInputEvent`](crate::input::TextInputEvent)s. Applied to the window by the windowing backend.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Ime {
+    /// Whether the IME may compose text, e.g. while a text field is focused. Off by default, as it would otherwise
+    /// capture keys meant for gameplay.
+    pub allowed: bool,
+    /// Where to place the IME's candidate window, in physical pixels from the top-left corner of the window.
+    pub position: Vec2,
+}
+
+/// A backend for [`Clipboard`], e.g. [`ArboardClipboard`](crate::input::ArboardClipboard) or [`MemoryClipboard`].
+pub trait ClipboardBackend: 'static {
+    /// The clipboard's text, or `None` if it's empty, holds something else or can't be read.
+    fn get(&mut self) -> Option<String>;
+
+    fn set(&mut self, text: String);
+}
+
+/// The system clipboard, or an in-memory one if it's unavailable. Set with
+/// [`AppExt::clipboard`](crate::input::AppExt::clipboard). Non-send, as some backends are.
+pub struct Clipboard {
+    backend: Box<dyn ClipboardBackend>,
+}
+
+impl Clipboard {
+    #[inline]
+    pub fn new(backend: impl ClipboardBackend) -> Self {
+        Self {
+            backend: Box::new(backend),
+        }
+    }
+
+    #[inline]
+    pub fn get(&mut self) -> Option<String> {
+        self.backend.get()
+    }
+
+    #[inline]
+    pub fn set(&mut self, text: impl Into<String>) {
+        self.backend.set(text.into());
+    }
+}
+
+impl Default for Clipboard {
+    #[inline]
+    fn default() -> Self {
+        Self::new(MemoryClipboard::default())
+    }
+}
+
+/// A [`ClipboardBackend`] private to the app, used where there's no system clipboard.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryClipboard {
+    text: Option<String>,
+}
+
+impl ClipboardBackend for MemoryClipboard {
+    #[inline]
+    fn get(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    #[inline]
+    fn set(&mut self, text: String) {
+        self.text = Some(text);
+    }
+}
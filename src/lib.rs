@@ -5,8 +5,12 @@
 
 #[cfg(feature = "asset")]
 pub mod asset;
+#[cfg(feature = "console")]
+pub mod console;
 #[cfg(feature = "core")]
 pub mod core;
+#[cfg(feature = "diagnostic")]
+pub mod diagnostic;
 #[cfg(feature = "g2d")]
 pub mod g2d;
 #[cfg(feature = "graphics")]
@@ -15,6 +19,16 @@ pub mod graphics;
 pub mod input;
 #[cfg(feature = "log")]
 pub mod log;
+#[cfg(feature = "profiler")]
+pub mod profiler;
+#[cfg(feature = "replay")]
+pub mod replay;
+#[cfg(feature = "save")]
+pub mod save;
+#[cfg(feature = "scene")]
+pub mod scene;
+#[cfg(feature = "transform")]
+pub mod transform;
 #[cfg(feature = "winit")]
 pub mod winit;
 
@@ -31,6 +45,7 @@ pub mod re_exports {
     pub use ::downcast_rs;
     pub use ::futures_lite;
     pub use ::parking_lot;
+    pub use ::serde;
     pub use ::smallvec;
     pub use ::thiserror;
 
@@ -40,6 +55,8 @@ pub mod re_exports {
     pub use crate::graphics::re_exports::*;
     #[cfg(feature = "log")]
     pub use crate::log::re_exports::*;
+    #[cfg(feature = "scene")]
+    pub use crate::scene::re_exports::*;
     #[cfg(feature = "winit")]
     pub use crate::winit::re_exports::*;
 }
@@ -52,16 +69,30 @@ pub mod prelude {
 
     #[cfg(feature = "asset")]
     pub use crate::asset::prelude::*;
+    #[cfg(feature = "console")]
+    pub use crate::console::prelude::*;
     #[cfg(feature = "core")]
     #[cfg(feature = "g2d")]
     pub use crate::g2d::prelude::*;
     pub use crate::core::prelude::*;
+    #[cfg(feature = "diagnostic")]
+    pub use crate::diagnostic::prelude::*;
     #[cfg(feature = "graphics")]
     pub use crate::graphics::prelude::*;
     #[cfg(feature = "input")]
     pub use crate::input::prelude::*;
     #[cfg(feature = "log")]
     pub use crate::log::prelude::*;
+    #[cfg(feature = "profiler")]
+    pub use crate::profiler::prelude::*;
+    #[cfg(feature = "replay")]
+    pub use crate::replay::prelude::*;
+    #[cfg(feature = "save")]
+    pub use crate::save::prelude::*;
+    #[cfg(feature = "scene")]
+    pub use crate::scene::prelude::*;
+    #[cfg(feature = "transform")]
+    pub use crate::transform::prelude::*;
     #[cfg(feature = "winit")]
     pub use crate::winit::prelude::*;
 
@@ -86,20 +117,57 @@ pub mod prelude {
 pub struct AVocado;
 
 #[cfg(feature = "core")]
-impl core::Subsystem for AVocado {
-    fn init(app: &mut core::App) {
+impl AVocado {
+    /// Every subsystem enabled by crate features. Use this to disable or replace some of them, e.g. swapping
+    /// [`log::LogSubsystem`] with your own logger.
+    pub fn group() -> core::SubsystemGroup {
+        let group = core::SubsystemGroup::new();
+
         #[cfg(feature = "log")]
-        app.init::<log::LogSubsystem>();
+        let group = group.add::<log::LogSubsystem>();
 
-        app.init::<core::CoreSubsystem>();
+        let group = group.add::<core::CoreSubsystem>();
+        #[cfg(feature = "profiler")]
+        let group = group.add::<profiler::ProfilerSubsystem>();
+        #[cfg(feature = "console")]
+        let group = group.add::<console::ConsoleSubsystem>();
+        #[cfg(feature = "diagnostic")]
+        let group = group.add::<diagnostic::DiagnosticSubsystem>();
+        #[cfg(feature = "transform")]
+        let group = group.add::<transform::TransformSubsystem>();
+        #[cfg(feature = "replay")]
+        let group = group.add::<replay::ReplaySubsystem>();
 
         #[cfg(feature = "asset")]
-        app.init::<asset::AssetSubsystem>();
+        let group = group.add::<asset::AssetSubsystem>();
+        #[cfg(feature = "scene")]
+        let group = group.add::<scene::SceneSubsystem>();
         #[cfg(feature = "graphics")]
-        app.init::<graphics::GraphicsSubsystem>();
+        let group = group.add::<graphics::GraphicsSubsystem>();
+        #[cfg(feature = "save")]
+        let group = group.add::<save::SaveSubsystem>();
         #[cfg(feature = "winit")]
-        app.init::<winit::WinitSubsystem>();
+        let group = group.add::<winit::WinitSubsystem>();
         #[cfg(feature = "g2d")]
-        app.init::<g2d::G2dSubsystem>();
+        let group = group.add::<g2d::G2dSubsystem>();
+
+        group
+    }
+}
+
+#[cfg(feature = "core")]
+impl core::Subsystem for AVocado {
+    /// Skips windowing and rendering subsystems if `--headless` was passed.
+    fn init(app: &mut core::App) {
+        #[allow(unused_mut)]
+        let mut group = Self::group();
+        if app.args().flag("headless") {
+            #[cfg(feature = "g2d")]
+            { group = group.disable::<g2d::G2dSubsystem>(); }
+            #[cfg(feature = "winit")]
+            { group = group.disable::<winit::WinitSubsystem>(); }
+        }
+
+        app.init_group(group);
     }
 }
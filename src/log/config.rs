@@ -1,3 +1,4 @@
+use crate::core::prelude::*;
 use bevy_utils::HashMap;
 use env_logger::{
     fmt::{
@@ -9,7 +10,10 @@ use log::{
     LevelFilter,
     Record,
 };
-use std::io;
+use std::{
+    io,
+    str::FromStr,
+};
 
 pub struct LogConfig {
     pub formatter: Option<Box<dyn Fn(
@@ -31,6 +35,26 @@ pub struct LogConfig {
 }
 
 impl LogConfig {
+    /// Reads `log.level` and the `log.modules` table, mapping module names to levels.
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        if let Some(level) = settings.get::<String>("log.level") {
+            match LevelFilter::from_str(&level) {
+                Ok(level) => self.filter = level,
+                Err(_) => log::warn!("Invalid log level `{}`", level),
+            }
+        }
+
+        if let Some(modules) = settings.get::<HashMap<String, String>>("log.modules") {
+            for (module, level) in modules {
+                match LevelFilter::from_str(&level) {
+                    // Module filters are only ever read once, so leaking them is fine.
+                    Ok(level) => { self.filter_modules.insert(Box::leak(module.into_boxed_str()), level); },
+                    Err(_) => log::warn!("Invalid log level `{}` for module {}", level, module),
+                }
+            }
+        }
+    }
+
     fn level_filter() -> LevelFilter {
         if cfg!(debug_assertions) {
             LevelFilter::Trace
@@ -70,3 +94,20 @@ impl Default for LogConfig {
         }
     }
 }
+
+/// The global log level, adjustable at runtime. Module filters from [`LogConfig`] still apply on top of it.
+pub struct LogLevel;
+impl LogLevel {
+    #[inline]
+    pub fn get() -> LevelFilter {
+        log::max_level()
+    }
+
+    /// Sets the global log level, writing it back to `settings` if given.
+    pub fn set(level: LevelFilter, settings: Option<&mut Settings>) {
+        log::set_max_level(level);
+        if let Some(settings) = settings {
+            settings.set("log.level", level.as_str().to_lowercase());
+        }
+    }
+}
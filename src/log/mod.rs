@@ -1,5 +1,12 @@
 use crate::core::prelude::*;
-use env_logger::Builder;
+use env_logger::{
+    Builder, Logger,
+};
+use log::{
+    Log, LevelFilter,
+    Metadata, Record,
+};
+use std::env;
 
 mod config;
 
@@ -12,18 +19,21 @@ pub mod re_exports {
 pub mod prelude {
     pub use crate::log::{
         LogSubsystem,
-        LogConfig,
+        LogConfig, LogLevel,
     };
 }
 
 pub struct LogSubsystem;
 impl Subsystem for LogSubsystem {
     fn init(app: &mut App) {
-        let config = match app.remove_res_ns::<LogConfig>() {
+        let mut config = match app.remove_res_ns::<LogConfig>() {
             Some(config) => config,
             None => LogConfig::default(),
         };
 
+        let settings = app.settings().clone();
+        config.apply_settings(&settings);
+
         let mut builder = Builder::new();
         builder
             .format_level(config.format_level)
@@ -32,7 +42,8 @@ impl Subsystem for LogSubsystem {
             .format_indent(config.format_indent)
             .format_timestamp(config.format_time)
 
-            .filter_level(config.filter)
+            // The global level is enforced by `LogLevel` instead, so it can be raised at runtime.
+            .filter_level(LevelFilter::Trace)
 
             .target(config.target)
             .write_style(config.style);
@@ -49,9 +60,37 @@ impl Subsystem for LogSubsystem {
             builder.filter_module(module, level);
         }
 
-        match builder.parse_default_env().try_init() {
-            Ok(_) => log::info!("Successfully initialized logger"),
+        let from_env = env::var_os("RUST_LOG").is_some();
+        let logger = builder.parse_default_env().build();
+        let filter = if from_env { logger.filter() } else { config.filter };
+
+        match log::set_boxed_logger(Box::new(HistoryLogger(logger))) {
+            Ok(_) => {
+                log::set_max_level(filter);
+                log::info!("Successfully initialized logger");
+            },
             Err(err) => log::error!("Couldn't initialize logger: {}", err),
         }
     }
 }
+
+/// Forwards to `env_logger`, additionally recording every line for [`CrashReporter`].
+struct HistoryLogger(Logger);
+impl Log for HistoryLogger {
+    #[inline]
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.0.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.0.matches(record) {
+            self.0.log(record);
+            CrashReporter::record_log(format!("[{} {}] {}", record.level(), record.target(), record.args()));
+        }
+    }
+
+    #[inline]
+    fn flush(&self) {
+        self.0.flush();
+    }
+}
@@ -0,0 +1,73 @@
+use crate::core::prelude::*;
+use bevy_utils::tracing;
+use std::{
+    path::PathBuf,
+    sync::Arc,
+};
+
+mod profiler;
+mod subscriber;
+
+pub use profiler::*;
+pub(crate) use subscriber::*;
+
+pub mod prelude {
+    pub use crate::profiler::{
+        ProfilerSubsystem,
+        ProfilerConfig, Profiler,
+        FrameProfile, ProfileRecord, ProfileKind,
+    };
+}
+
+/// Configures [`ProfilerSubsystem`]. Must be inserted before the subsystem is initialized.
+#[derive(Resource, Debug, Clone)]
+pub struct ProfilerConfig {
+    /// Whether to start recording right away.
+    pub enabled: bool,
+    /// How many frames [`Profiler`] keeps.
+    pub capacity: usize,
+    /// Where to write the history as Chrome trace JSON on exit; overridden by `--profile`.
+    pub export: Option<PathBuf>,
+}
+
+impl Default for ProfilerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            capacity: 300,
+            export: None,
+        }
+    }
+}
+
+/// Installs a global `tracing` subscriber recording `bevy_ecs` stage and system spans, and render node spans. Fails
+/// to record anything if another subscriber is installed first.
+pub struct ProfilerSubsystem;
+impl Subsystem for ProfilerSubsystem {
+    fn init(app: &mut App) {
+        app.arg(ArgDesc::opt("profile", "PATH").help("Exports a Chrome trace of the last frames to this file on exit"));
+
+        let mut config = app.res_or(ProfilerConfig::default).clone();
+        if let Some(path) = app.args().value("profile") {
+            config.export = Some(PathBuf::from(path));
+            app.insert_res(config.clone());
+        }
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let collector = Arc::new(Collector::new(sender));
+        collector.set_enabled(config.enabled);
+
+        if tracing::subscriber::set_global_default(ProfilerSubscriber(Arc::clone(&collector))).is_err() {
+            log::warn!("Another tracing subscriber is installed; the profiler won't record anything");
+        }
+
+        app
+            .insert_res(Profiler::new(collector, receiver, config.capacity))
+            .sys(CoreStage::SysUpdate, Profiler::update_sys.at_start())
+            .sys(ShutdownStage, Profiler::export_sys);
+    }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![SubsystemId::of::<CoreSubsystem>()]
+    }
+}
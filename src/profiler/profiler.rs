@@ -0,0 +1,246 @@
+use crate::profiler::{
+    Collector, ProfilerConfig,
+};
+use bevy_ecs::prelude::*;
+use bevy_utils::{
+    Duration, HashMap,
+};
+use crossbeam_channel::Receiver;
+use std::{
+    collections::VecDeque,
+    fmt::Write as _,
+    fs, io,
+    path::Path,
+    sync::Arc,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ProfileKind {
+    Stage,
+    System,
+    RenderNode,
+}
+
+impl ProfileKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Stage => "stage",
+            Self::System => "system",
+            Self::RenderNode => "render_node",
+        }
+    }
+}
+
+/// CPU time spent in one stage, system or render node. Times are relative to when the profiler was created.
+#[derive(Debug, Clone)]
+pub struct ProfileRecord {
+    pub kind: ProfileKind,
+    pub name: Arc<str>,
+    /// Index into [`Profiler::threads`].
+    pub thread: u32,
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+/// Every record of a single frame, from the start of its first stage to the start of the next frame.
+#[derive(Debug, Clone)]
+pub struct FrameProfile {
+    pub index: u64,
+    pub start: Duration,
+    pub duration: Duration,
+    /// The thread the stages ran on.
+    pub thread: u32,
+    pub records: Vec<ProfileRecord>,
+}
+
+impl FrameProfile {
+    #[inline]
+    pub fn iter(&self, kind: ProfileKind) -> impl Iterator<Item = &ProfileRecord> {
+        self.records.iter().filter(move |record| record.kind == kind)
+    }
+
+    /// Total time spent in `name`, e.g. a system that ran more than once in this frame.
+    pub fn total(&self, kind: ProfileKind, name: &str) -> Duration {
+        self.iter(kind)
+            .filter(|record| &*record.name == name)
+            .map(|record| record.duration)
+            .sum()
+    }
+
+    /// Total time spent in each stage, system or render node, longest first.
+    pub fn totals(&self, kind: ProfileKind) -> Vec<(Arc<str>, Duration)> {
+        let mut totals = HashMap::<Arc<str>, Duration>::default();
+        for record in self.iter(kind) {
+            *totals.entry(Arc::clone(&record.name)).or_default() += record.duration;
+        }
+
+        let mut totals = totals.into_iter().collect::<Vec<_>>();
+        totals.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+        totals
+    }
+}
+
+/// Records CPU time per stage, system and render node each frame, keeping the last
+/// [`capacity`](Profiler::capacity) frames. Frames are available once the next one begins.
+#[derive(Resource)]
+pub struct Profiler {
+    collector: Arc<Collector>,
+    receiver: Receiver<FrameProfile>,
+    history: VecDeque<FrameProfile>,
+    capacity: usize,
+}
+
+impl Profiler {
+    pub fn update_sys(mut profiler: ResMut<Self>) {
+        let profiler = &mut *profiler;
+        for frame in profiler.receiver.try_iter() {
+            if profiler.history.len() >= profiler.capacity {
+                profiler.history.pop_front();
+            }
+
+            profiler.history.push_back(frame);
+        }
+    }
+
+    /// Writes the history to [`ProfilerConfig::export`] if set; runs in [`crate::core::ShutdownStage`].
+    pub fn export_sys(profiler: Res<Self>, config: Res<ProfilerConfig>) {
+        if let Some(ref path) = config.export {
+            match profiler.export(path) {
+                Ok(()) => log::info!("Exported {} profiled frames to {:?}", profiler.history.len(), path),
+                Err(err) => log::warn!("Couldn't export profile to {:?}: {}", path, err),
+            }
+        }
+    }
+
+    pub(crate) fn new(collector: Arc<Collector>, receiver: Receiver<FrameProfile>, capacity: usize) -> Self {
+        Self {
+            collector, receiver,
+            history: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.collector.is_enabled()
+    }
+
+    /// Pauses or resumes recording; the history is kept either way.
+    #[inline]
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.collector.set_enabled(enabled);
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.history.len() > self.capacity {
+            self.history.pop_front();
+        }
+    }
+
+    /// Recorded frames, oldest first.
+    #[inline]
+    pub fn history(&self) -> &VecDeque<FrameProfile> {
+        &self.history
+    }
+
+    #[inline]
+    pub fn last(&self) -> Option<&FrameProfile> {
+        self.history.back()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+
+    /// Names of the threads records ran on, indexed by [`ProfileRecord::thread`].
+    #[inline]
+    pub fn threads(&self) -> Vec<String> {
+        self.collector.threads()
+    }
+
+    /// Average time per frame spent in `name` over the history.
+    pub fn average(&self, kind: ProfileKind, name: &str) -> Duration {
+        if self.history.is_empty() {
+            Duration::ZERO
+        } else {
+            self.history.iter().map(|frame| frame.total(kind, name)).sum::<Duration>() / self.history.len() as u32
+        }
+    }
+
+    /// Formats the history as Chrome trace event JSON, viewable in `chrome://tracing` or Perfetto.
+    pub fn to_chrome_trace(&self) -> String {
+        let mut json = String::from("{\"displayTimeUnit\":\"ms\",\"traceEvents\":[");
+        let mut first = true;
+        let mut event = |json: &mut String| if first {
+            first = false;
+        } else {
+            json.push(',');
+        };
+
+        for (tid, name) in self.threads().iter().enumerate() {
+            event(&mut json);
+            let _ = write!(
+                json, "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":0,\"tid\":{},\"args\":{{\"name\":\"{}\"}}}}",
+                tid, escape(name),
+            );
+        }
+
+        for frame in &self.history {
+            event(&mut json);
+            let _ = write!(
+                json, "{{\"name\":\"Frame {}\",\"cat\":\"frame\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":0,\"tid\":{}}}",
+                frame.index, micros(frame.start), micros(frame.duration), frame.thread,
+            );
+
+            for record in &frame.records {
+                event(&mut json);
+                let _ = write!(
+                    json, "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":0,\"tid\":{}}}",
+                    escape(&record.name), record.kind.as_str(), micros(record.start), micros(record.duration), record.thread,
+                );
+            }
+        }
+
+        json.push_str("]}");
+        json
+    }
+
+    /// Writes [`to_chrome_trace`](Profiler::to_chrome_trace) to `path`.
+    pub fn export(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, self.to_chrome_trace())
+    }
+}
+
+#[inline]
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => { let _ = write!(escaped, "\\u{:04x}", c as u32); },
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,279 @@
+use crate::profiler::{
+    FrameProfile, ProfileKind, ProfileRecord,
+};
+use bevy_utils::{
+    tracing::{
+        field::{
+            Field, Visit,
+        },
+        span::{
+            Attributes, Id, Record,
+        },
+        subscriber::Interest,
+        Event, Metadata, Subscriber,
+    },
+    HashMap, HashSet, Instant,
+};
+use crossbeam_channel::Sender;
+use parking_lot::Mutex;
+use std::{
+    cell::Cell,
+    fmt::Debug,
+    mem,
+    sync::{
+        atomic::{
+            AtomicBool, AtomicU64,
+            Ordering,
+        },
+        Arc,
+    },
+    thread,
+};
+
+thread_local! {
+    static THREAD: Cell<Option<u32>> = const { Cell::new(None) };
+    static STAGE_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Collects the spans `bevy_ecs` emits for stages and systems, plus the ones emitted for render nodes, into
+/// frames. A frame ends as soon as a top-level stage that already ran in it is entered again.
+pub(crate) struct Collector {
+    epoch: Instant,
+    enabled: AtomicBool,
+    next_id: AtomicU64,
+
+    spans: Mutex<HashMap<u64, SpanData>>,
+    threads: Mutex<Vec<String>>,
+    frame: Mutex<FrameBuilder>,
+    sender: Sender<FrameProfile>,
+}
+
+struct SpanData {
+    kind: ProfileKind,
+    name: Arc<str>,
+    refs: usize,
+    entered: Option<(Instant, u32)>,
+}
+
+#[derive(Default)]
+struct FrameBuilder {
+    index: u64,
+    start: Option<(Instant, u32)>,
+    stages: HashSet<Arc<str>>,
+    records: Vec<ProfileRecord>,
+}
+
+impl Collector {
+    pub fn new(sender: Sender<FrameProfile>) -> Self {
+        Self {
+            epoch: Instant::now(),
+            enabled: AtomicBool::new(true),
+            next_id: AtomicU64::new(1),
+
+            spans: Mutex::default(),
+            threads: Mutex::default(),
+            frame: Mutex::default(),
+            sender,
+        }
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Stops or resumes recording. Disabling discards the frame in progress.
+    pub fn set_enabled(&self, enabled: bool) {
+        if self.enabled.swap(enabled, Ordering::Relaxed) && !enabled {
+            *self.frame.lock() = FrameBuilder::default();
+        }
+    }
+
+    /// Names of every thread that recorded a span, indexed by [`ProfileRecord::thread`].
+    #[inline]
+    pub fn threads(&self) -> Vec<String> {
+        self.threads.lock().clone()
+    }
+
+    fn thread(&self) -> u32 {
+        THREAD.with(|index| match index.get() {
+            Some(index) => index,
+            None => {
+                let mut threads = self.threads.lock();
+                let current = threads.len() as u32;
+                threads.push(match thread::current().name() {
+                    Some(name) => name.to_string(),
+                    None => format!("Thread {}", current),
+                });
+
+                index.set(Some(current));
+                current
+            },
+        })
+    }
+
+    fn begin_stage(&self, name: &Arc<str>, now: Instant, thread: u32) {
+        let mut frame = self.frame.lock();
+        if frame.stages.contains(name) {
+            let (start, main) = frame.start.unwrap();
+            let profile = FrameProfile {
+                index: frame.index,
+                start: start - self.epoch,
+                duration: now - start,
+                thread: main,
+                records: mem::take(&mut frame.records),
+            };
+
+            frame.index += 1;
+            frame.start = None;
+            frame.stages.clear();
+
+            let _ = self.sender.send(profile);
+        }
+
+        if frame.start.is_none() {
+            frame.start = Some((now, thread));
+        }
+
+        frame.stages.insert(Arc::clone(name));
+    }
+}
+
+pub(crate) struct ProfilerSubscriber(pub Arc<Collector>);
+impl ProfilerSubscriber {
+    fn kind(metadata: &Metadata) -> Option<ProfileKind> {
+        match metadata.name() {
+            "stage" => Some(ProfileKind::Stage),
+            "system" | "exclusive_system" => Some(ProfileKind::System),
+            "render_node" => Some(ProfileKind::RenderNode),
+            _ => None,
+        }
+    }
+}
+
+impl Subscriber for ProfilerSubscriber {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if metadata.is_span() && Self::kind(metadata).is_some() {
+            Interest::always()
+        } else {
+            Interest::never()
+        }
+    }
+
+    #[inline]
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.is_span() && Self::kind(metadata).is_some()
+    }
+
+    fn new_span(&self, span: &Attributes) -> Id {
+        let mut name = NameVisitor(None);
+        span.record(&mut name);
+
+        let id = self.0.next_id.fetch_add(1, Ordering::Relaxed);
+        self.0.spans.lock().insert(id, SpanData {
+            kind: Self::kind(span.metadata()).unwrap(),
+            name: name.0.unwrap_or_else(|| span.metadata().name().to_string()).into(),
+            refs: 1,
+            entered: None,
+        });
+
+        Id::from_u64(id)
+    }
+
+    #[inline]
+    fn record(&self, _: &Id, _: &Record) {}
+
+    #[inline]
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    #[inline]
+    fn event(&self, _: &Event) {}
+
+    fn enter(&self, span: &Id) {
+        let now = Instant::now();
+        let thread = self.0.thread();
+
+        let mut spans = self.0.spans.lock();
+        let Some(data) = spans.get_mut(&span.into_u64()) else { return };
+        data.entered = Some((now, thread));
+
+        if data.kind == ProfileKind::Stage {
+            let depth = STAGE_DEPTH.with(|depth| depth.replace(depth.get() + 1));
+            if depth == 0 && self.0.is_enabled() {
+                let name = Arc::clone(&data.name);
+                drop(spans);
+
+                self.0.begin_stage(&name, now, thread);
+            }
+        }
+    }
+
+    fn exit(&self, span: &Id) {
+        let now = Instant::now();
+
+        let mut spans = self.0.spans.lock();
+        let Some(data) = spans.get_mut(&span.into_u64()) else { return };
+        let Some((start, thread)) = data.entered.take() else { return };
+
+        if data.kind == ProfileKind::Stage {
+            STAGE_DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+        }
+
+        if !self.0.is_enabled() {
+            return;
+        }
+
+        let record = ProfileRecord {
+            kind: data.kind,
+            name: Arc::clone(&data.name),
+            thread,
+            start: start - self.0.epoch,
+            duration: now - start,
+        };
+
+        drop(spans);
+
+        let mut frame = self.0.frame.lock();
+        if frame.start.is_some() {
+            frame.records.push(record);
+        }
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Some(data) = self.0.spans.lock().get_mut(&span.into_u64()) {
+            data.refs += 1;
+        }
+
+        span.clone()
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        let mut spans = self.0.spans.lock();
+        let id = span.into_u64();
+
+        let Some(data) = spans.get_mut(&id) else { return false };
+        data.refs -= 1;
+
+        if data.refs == 0 {
+            spans.remove(&id);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+struct NameVisitor(Option<String>);
+impl Visit for NameVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "name" {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::input::{
+    KeyCode, MouseButton, MouseWheelUnit,
+    KeyEvent, KeyModifierEvent, TextInputEvent, GamepadEvent,
+};
+use serde::{
+    Deserialize, Serialize,
+};
+use std::time::Duration;
+
+/// First line of a replay file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    pub version: u32,
+}
+
+impl ReplayHeader {
+    pub const VERSION: u32 = 5;
+}
+
+/// Everything that happened during a single frame. Replay files store one per line after the header, so a
+/// recording cut short by a crash is still readable.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    pub delta: Duration,
+    pub events: Vec<ReplayEvent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReplayEvent {
+    Key {
+        pressed: bool,
+        #[serde(default)]
+        repeat: bool,
+        key: Option<KeyCode>,
+        physical: Option<KeyCode>,
+        scancode: u32,
+    },
+    Modifiers {
+        alt: bool,
+        ctrl: bool,
+        logo: bool,
+        shift: bool,
+    },
+    Text(TextInputEvent),
+    MouseButton {
+        pressed: bool,
+        button: MouseButton,
+    },
+    CursorMoved {
+        x: f32,
+        y: f32,
+    },
+    CursorEntered,
+    CursorLeft,
+    MouseMotion {
+        x: f32,
+        y: f32,
+    },
+    MouseWheel {
+        x: f32,
+        y: f32,
+        unit: MouseWheelUnit,
+    },
+    Gamepad(GamepadEvent),
+    WindowResized {
+        width: u32,
+        height: u32,
+    },
+    WindowMoved {
+        x: i32,
+        y: i32,
+    },
+    Suspend,
+    Resume,
+}
+
+impl From<&KeyEvent> for ReplayEvent {
+    #[inline]
+    fn from(event: &KeyEvent) -> Self {
+        Self::Key {
+            pressed: event.pressed,
+            repeat: event.repeat,
+            key: event.key,
+            physical: event.physical,
+            scancode: event.scancode,
+        }
+    }
+}
+
+impl From<&KeyModifierEvent> for ReplayEvent {
+    #[inline]
+    fn from(event: &KeyModifierEvent) -> Self {
+        Self::Modifiers {
+            alt: event.alt,
+            ctrl: event.ctrl,
+            logo: event.logo,
+            shift: event.shift,
+        }
+    }
+}
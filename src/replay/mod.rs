@@ -0,0 +1,98 @@
+use crate::core::prelude::*;
+use std::path::PathBuf;
+
+mod frame;
+mod player;
+mod recorder;
+
+pub use frame::*;
+pub use player::*;
+pub use recorder::*;
+
+pub mod prelude {
+    pub use crate::replay::{
+        ReplaySubsystem,
+        ReplayConfig, ReplayRecorder, ReplayPlayer,
+    };
+}
+
+/// Configures [`ReplaySubsystem`]. Must be inserted before the subsystem is initialized.
+#[derive(Resource, Debug, Clone)]
+pub struct ReplayConfig {
+    /// Records the session to this file; overridden by `--record`.
+    pub record: Option<PathBuf>,
+    /// Plays this file back instead of taking live input; overridden by `--replay`.
+    pub play: Option<PathBuf>,
+    /// Whether to exit once the replay ends.
+    pub exit_on_end: bool,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            record: None,
+            play: None,
+            exit_on_end: true,
+        }
+    }
+}
+
+/// Records key, text and mouse events, window events and frame deltas to a file, or plays one back so that e.g. a `--headless`
+/// run reproduces the session frame-for-frame. Does nothing unless a file to record or play is given.
+pub struct ReplaySubsystem;
+impl Subsystem for ReplaySubsystem {
+    fn init(app: &mut App) {
+        app
+            .arg(ArgDesc::opt("record", "PATH").help("Records input and frame times to this replay file"))
+            .arg(ArgDesc::opt("replay", "PATH").help("Plays back this replay file instead of taking live input"));
+
+        let mut config = app.res_or(ReplayConfig::default).clone();
+        if let Some(path) = app.args().value("record") {
+            config.record = Some(PathBuf::from(path));
+        }
+
+        if let Some(path) = app.args().value("replay") {
+            config.play = Some(PathBuf::from(path));
+        }
+
+        app
+            .insert_res(config.clone())
+            .event::<crate::input::KeyEvent>()
+            .event::<crate::input::KeyModifierEvent>()
+            .event::<crate::input::TextInputEvent>()
+            .event::<crate::input::MouseButtonEvent>()
+            .event::<crate::input::MouseMotionEvent>()
+            .event::<crate::input::MouseWheelEvent>()
+            .event::<crate::input::CursorMovedEvent>()
+            .event::<crate::input::CursorEnteredEvent>()
+            .event::<crate::input::CursorLeftEvent>()
+            .event::<crate::input::GamepadEvent>();
+
+        if let Some(ref path) = config.play {
+            match ReplayPlayer::load(path, config.exit_on_end) {
+                Ok(player) => {
+                    log::info!("Playing {} replay frames from {:?}", player.remaining(), path);
+                    app.res_ns_or(crate::input::GamepadSources::default).set_paused(true);
+                    app
+                        .insert_res(player)
+                        .sys(CoreStage::SysUpdate, ReplayPlayer::play_sys.at_start());
+                },
+                Err(err) => log::error!("Couldn't load replay {:?}: {}", path, err),
+            }
+        } else if let Some(ref path) = config.record {
+            match ReplayRecorder::create(path) {
+                Ok(recorder) => {
+                    log::info!("Recording replay to {:?}", path);
+                    app
+                        .insert_res(recorder)
+                        .sys(CoreStage::SysUpdate, ReplayRecorder::record_sys.at_end());
+                },
+                Err(err) => log::error!("Couldn't create replay {:?}: {}", path, err),
+            }
+        }
+    }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![SubsystemId::of::<CoreSubsystem>()]
+    }
+}
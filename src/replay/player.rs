@@ -0,0 +1,164 @@
+use crate::{
+    core::prelude::*,
+    input::{
+        KeyEvent, KeyModifierEvent, TextInputEvent,
+        MouseButtonEvent, CursorMovedEvent, CursorEnteredEvent, CursorLeftEvent, MouseMotionEvent, MouseWheelEvent,
+        GamepadEvent,
+    },
+    replay::{
+        ReplayEvent, ReplayFrame, ReplayHeader,
+    },
+};
+use bevy_math::Vec2;
+use std::{
+    collections::VecDeque,
+    fs,
+    path::Path,
+};
+
+/// Feeds a recorded replay file back through the same event channels, one frame per update, with [`Time`]
+/// advancing by the recorded deltas. Runs at the start of [`CoreStage::SysUpdate`] so readers see the events
+/// within the same frame they were recorded in.
+///
+/// Live key and mouse events are discarded and gamepad sources are paused while playing. Window events are only
+/// fed back when running without a window, as a real window reports its own.
+#[derive(Resource, Debug)]
+pub struct ReplayPlayer {
+    frames: VecDeque<ReplayFrame>,
+    played: u64,
+    exit_on_end: bool,
+    finished: bool,
+}
+
+impl ReplayPlayer {
+    pub fn play_sys(world: &mut World) {
+        world.resource_scope(|world, mut player: Mut<Self>| player.play(world));
+    }
+
+    /// Reads a replay file written by [`crate::replay::ReplayRecorder`].
+    pub fn load(path: &Path, exit_on_end: bool) -> Result<Self, anyhow::Error> {
+        let source = fs::read_to_string(path)?;
+        let mut lines = source.lines().filter(|line| !line.trim().is_empty());
+
+        let header = ron::de::from_str::<ReplayHeader>(lines.next().unwrap_or_default())?;
+        if header.version != ReplayHeader::VERSION {
+            anyhow::bail!("Unsupported replay version {}; expected {}", header.version, ReplayHeader::VERSION);
+        }
+
+        let frames = lines
+            .enumerate()
+            .map(|(i, line)| ron::de::from_str(line).map_err(|err| anyhow::anyhow!("Invalid frame {}: {}", i, err)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            frames,
+            played: 0,
+            exit_on_end,
+            finished: false,
+        })
+    }
+
+    /// How many frames were played so far.
+    #[inline]
+    pub fn played(&self) -> u64 {
+        self.played
+    }
+
+    /// How many frames are left to play.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn play(&mut self, world: &mut World) {
+        if self.finished {
+            return;
+        }
+
+        Self::clear::<KeyEvent>(world);
+        Self::clear::<KeyModifierEvent>(world);
+        Self::clear::<TextInputEvent>(world);
+        Self::clear::<MouseButtonEvent>(world);
+        Self::clear::<CursorMovedEvent>(world);
+        Self::clear::<CursorEnteredEvent>(world);
+        Self::clear::<CursorLeftEvent>(world);
+        Self::clear::<MouseMotionEvent>(world);
+        Self::clear::<MouseWheelEvent>(world);
+        Self::clear::<GamepadEvent>(world);
+
+        let Some(frame) = self.frames.pop_front() else {
+            log::info!("Replay finished after {} frames", self.played);
+            self.finished = true;
+
+            if self.exit_on_end {
+                world.send_event(ExitEvent::graceful());
+            }
+
+            return;
+        };
+
+        world.resource_mut::<Time>().set_next_delta(frame.delta);
+
+        #[cfg(feature = "winit")]
+        let windowed = world.get_non_send_resource::<crate::winit::WinitWindow>().is_some();
+
+        for event in frame.events {
+            match event {
+                ReplayEvent::Key { pressed, repeat, key, physical, scancode } => Self::send(world, KeyEvent { pressed, repeat, key, physical, scancode }),
+                ReplayEvent::Modifiers { alt, ctrl, logo, shift } => Self::send(world, KeyModifierEvent { alt, ctrl, logo, shift }),
+                ReplayEvent::Text(event) => Self::send(world, event),
+                ReplayEvent::MouseButton { pressed, button } => Self::send(world, MouseButtonEvent { pressed, button }),
+                ReplayEvent::CursorMoved { x, y } => Self::send(world, CursorMovedEvent(Vec2::new(x, y))),
+                ReplayEvent::CursorEntered => Self::send(world, CursorEnteredEvent),
+                ReplayEvent::CursorLeft => Self::send(world, CursorLeftEvent),
+                ReplayEvent::MouseMotion { x, y } => Self::send(world, MouseMotionEvent(Vec2::new(x, y))),
+                ReplayEvent::MouseWheel { x, y, unit } => Self::send(world, MouseWheelEvent { delta: Vec2::new(x, y), unit }),
+                ReplayEvent::Gamepad(event) => Self::send(world, event),
+
+                #[cfg(feature = "winit")]
+                ReplayEvent::WindowResized { width, height } => if !windowed {
+                    Self::send(world, crate::winit::WindowResizedEvent(winit::dpi::PhysicalSize { width, height }));
+                },
+                #[cfg(feature = "winit")]
+                ReplayEvent::WindowMoved { x, y } => if !windowed {
+                    Self::send(world, crate::winit::WindowMovedEvent(winit::dpi::PhysicalPosition { x, y }));
+                },
+                #[cfg(feature = "winit")]
+                ReplayEvent::Suspend => if !windowed {
+                    Self::send(world, crate::winit::SuspendEvent);
+                },
+                #[cfg(feature = "winit")]
+                ReplayEvent::Resume => if !windowed {
+                    Self::send(world, crate::winit::ResumeEvent);
+                },
+
+                #[cfg(not(feature = "winit"))]
+                ReplayEvent::WindowResized { .. } |
+                ReplayEvent::WindowMoved { .. } |
+                ReplayEvent::Suspend |
+                ReplayEvent::Resume => {},
+            }
+        }
+
+        self.played += 1;
+    }
+
+    #[inline]
+    fn send<E: Event>(world: &mut World, event: E) {
+        if let Some(mut events) = world.get_resource_mut::<Events<E>>() {
+            events.send(event);
+        }
+    }
+
+    #[inline]
+    fn clear<E: Event>(world: &mut World) {
+        if let Some(mut events) = world.get_resource_mut::<Events<E>>() {
+            events.clear();
+        }
+    }
+}
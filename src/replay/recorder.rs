@@ -0,0 +1,173 @@
+use crate::{
+    core::prelude::*,
+    input::{
+        KeyEvent, KeyModifierEvent, TextInputEvent,
+        MouseButtonEvent, CursorMovedEvent, CursorEnteredEvent, CursorLeftEvent, MouseMotionEvent, MouseWheelEvent,
+        GamepadEvent,
+    },
+    replay::{
+        ReplayEvent, ReplayFrame, ReplayHeader,
+    },
+};
+use bevy_ecs::event::ManualEventReader;
+use bevy_utils::default;
+use serde::Serialize;
+use std::{
+    fs::{
+        self,
+        File,
+    },
+    io::{
+        self,
+        BufWriter, Write,
+    },
+    path::Path,
+};
+
+/// Writes every input event, window event and [`Time`] delta to a replay file, one frame per line. Runs at the
+/// end of [`CoreStage::SysUpdate`], after the frame's time has been updated.
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    writer: BufWriter<File>,
+    frames: u64,
+    failed: bool,
+
+    keys: ManualEventReader<KeyEvent>,
+    modifiers: ManualEventReader<KeyModifierEvent>,
+    text: ManualEventReader<TextInputEvent>,
+    mouse: MouseReaders,
+    gamepad: ManualEventReader<GamepadEvent>,
+    #[cfg(feature = "winit")]
+    window: WindowReaders,
+}
+
+#[derive(Default)]
+struct MouseReaders {
+    button: ManualEventReader<MouseButtonEvent>,
+    cursor: ManualEventReader<CursorMovedEvent>,
+    entered: ManualEventReader<CursorEnteredEvent>,
+    left: ManualEventReader<CursorLeftEvent>,
+    motion: ManualEventReader<MouseMotionEvent>,
+    wheel: ManualEventReader<MouseWheelEvent>,
+}
+
+#[cfg(feature = "winit")]
+#[derive(Default)]
+struct WindowReaders {
+    resized: ManualEventReader<crate::winit::WindowResizedEvent>,
+    moved: ManualEventReader<crate::winit::WindowMovedEvent>,
+    suspend: ManualEventReader<crate::winit::SuspendEvent>,
+    resume: ManualEventReader<crate::winit::ResumeEvent>,
+}
+
+impl ReplayRecorder {
+    pub fn record_sys(world: &mut World) {
+        world.resource_scope(|world, mut recorder: Mut<Self>| recorder.record(world));
+    }
+
+    /// Creates the replay file and writes its header.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut recorder = Self {
+            writer: BufWriter::new(File::create(path)?),
+            frames: 0,
+            failed: false,
+
+            keys: default(),
+            modifiers: default(),
+            text: default(),
+            mouse: default(),
+            gamepad: default(),
+            #[cfg(feature = "winit")]
+            window: default(),
+        };
+
+        recorder.write_line(&ReplayHeader { version: ReplayHeader::VERSION })?;
+        Ok(recorder)
+    }
+
+    /// How many frames were recorded so far.
+    #[inline]
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn record(&mut self, world: &World) {
+        if self.failed {
+            return;
+        }
+
+        let mut frame = ReplayFrame {
+            delta: world.resource::<Time>().delta(),
+            events: vec![],
+        };
+
+        Self::read(world, &mut self.keys, &mut frame.events, |event| ReplayEvent::from(event));
+        Self::read(world, &mut self.modifiers, &mut frame.events, |event| ReplayEvent::from(event));
+        Self::read(world, &mut self.text, &mut frame.events, |event| ReplayEvent::Text(event.clone()));
+
+        let mouse = &mut self.mouse;
+        Self::read(world, &mut mouse.button, &mut frame.events, |event| ReplayEvent::MouseButton {
+            pressed: event.pressed,
+            button: event.button,
+        });
+        Self::read(world, &mut mouse.cursor, &mut frame.events, |event| ReplayEvent::CursorMoved {
+            x: event.x,
+            y: event.y,
+        });
+        Self::read(world, &mut mouse.entered, &mut frame.events, |_| ReplayEvent::CursorEntered);
+        Self::read(world, &mut mouse.left, &mut frame.events, |_| ReplayEvent::CursorLeft);
+        Self::read(world, &mut mouse.motion, &mut frame.events, |event| ReplayEvent::MouseMotion {
+            x: event.x,
+            y: event.y,
+        });
+        Self::read(world, &mut mouse.wheel, &mut frame.events, |event| ReplayEvent::MouseWheel {
+            x: event.delta.x,
+            y: event.delta.y,
+            unit: event.unit,
+        });
+        Self::read(world, &mut self.gamepad, &mut frame.events, |event| ReplayEvent::Gamepad(event.clone()));
+
+        #[cfg(feature = "winit")]
+        {
+            let window = &mut self.window;
+            Self::read(world, &mut window.resized, &mut frame.events, |event| ReplayEvent::WindowResized {
+                width: event.width,
+                height: event.height,
+            });
+            Self::read(world, &mut window.moved, &mut frame.events, |event| ReplayEvent::WindowMoved {
+                x: event.x,
+                y: event.y,
+            });
+            Self::read(world, &mut window.suspend, &mut frame.events, |_| ReplayEvent::Suspend);
+            Self::read(world, &mut window.resume, &mut frame.events, |_| ReplayEvent::Resume);
+        }
+
+        match self.write_line(&frame) {
+            Ok(()) => self.frames += 1,
+            Err(err) => {
+                log::error!("Couldn't write replay frame, recording stopped: {}", err);
+                self.failed = true;
+            },
+        }
+    }
+
+    fn read<E: Event>(
+        world: &World, reader: &mut ManualEventReader<E>, events: &mut Vec<ReplayEvent>,
+        map: impl Fn(&E) -> ReplayEvent,
+    ) {
+        if let Some(source) = world.get_resource::<Events<E>>() {
+            events.extend(reader.iter(source).map(map));
+        }
+    }
+
+    /// Flushes after every line, so the file is complete up to the last frame even if the app crashes.
+    fn write_line(&mut self, value: &impl Serialize) -> io::Result<()> {
+        let line = ron::ser::to_string(value).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{
+    graphics::Image,
+    save::SaveMeta,
+};
+
+/// Saves every [`Saveable`](crate::save::Saveable) entity to `slot`, overwriting it. See
+/// [`SaveManager::slot_path`](crate::save::SaveManager::slot_path) for valid slot names.
+#[derive(Debug, Clone)]
+pub struct SaveEvent {
+    pub slot: String,
+    pub thumbnail: Option<Image>,
+}
+
+impl SaveEvent {
+    #[inline]
+    pub fn new(slot: impl Into<String>) -> Self {
+        Self {
+            slot: slot.into(),
+            thumbnail: None,
+        }
+    }
+
+    #[inline]
+    pub fn with_thumbnail(self, thumbnail: Image) -> Self {
+        Self {
+            thumbnail: Some(thumbnail),
+            ..self
+        }
+    }
+}
+
+/// Replaces every [`Saveable`](crate::save::Saveable) entity with the contents of `slot`.
+#[derive(Debug, Clone)]
+pub struct LoadEvent {
+    pub slot: String,
+}
+
+impl LoadEvent {
+    #[inline]
+    pub fn new(slot: impl Into<String>) -> Self {
+        Self { slot: slot.into(), }
+    }
+}
+
+#[derive(Debug)]
+pub struct SaveDoneEvent {
+    pub slot: String,
+    pub result: Result<SaveMeta, String>,
+}
+
+#[derive(Debug)]
+pub struct LoadDoneEvent {
+    pub slot: String,
+    pub result: Result<SaveMeta, String>,
+}
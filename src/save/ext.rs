@@ -0,0 +1,25 @@
+use crate::{
+    core::prelude::*,
+    save::SaveManager,
+};
+
+pub trait AppExt {
+    /// Registers a migration that upgrades save bodies from schema version `from` to `from + 1`. Requires
+    /// [`SaveSubsystem`](crate::save::SaveSubsystem) to be initialized.
+    fn save_migration(
+        &mut self, from: u32,
+        migration: impl Fn(String) -> Result<String, anyhow::Error> + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl AppExt for App {
+    fn save_migration(
+        &mut self, from: u32,
+        migration: impl Fn(String) -> Result<String, anyhow::Error> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.res_mut::<SaveManager>()
+            .expect("`SaveSubsystem` must be initialized before registering save migrations")
+            .migration(from, migration);
+        self
+    }
+}
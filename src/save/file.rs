@@ -0,0 +1,143 @@
+use crate::graphics::Image;
+use serde::{
+    Deserialize, Serialize,
+};
+use std::{
+    fs::{
+        self,
+        File,
+    },
+    io::{
+        self,
+        Read, Write,
+    },
+    path::Path,
+    process,
+    sync::atomic::{
+        AtomicU64, Ordering,
+    },
+    time::{
+        Duration, SystemTime,
+    },
+};
+
+const SAVE_MAGIC: &[u8; 8] = b"AVSAVE\0\0";
+
+/// Distinguishes the temporary files of concurrent writes to the same slot.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Header of a save slot, readable without loading the rest of the save.
+#[derive(Debug, Clone)]
+pub struct SaveMeta {
+    /// Schema version the body was written with.
+    pub version: u32,
+    pub timestamp: SystemTime,
+    pub playtime: Duration,
+    pub thumbnail: Option<Image>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SaveHeader {
+    version: u32,
+    timestamp: Duration,
+    playtime: Duration,
+}
+
+/// Reads and writes the save file format: a magic number, a RON header, an optional PNG thumbnail, and the body,
+/// each prefixed with their little-endian `u32` length except the body.
+pub struct SaveFile;
+impl SaveFile {
+    /// Writes to a temporary file first and then renames it over `path`, so a crash never leaves a half-written
+    /// save behind. Every write has its own temporary file, so concurrent writes to the same path leave the last
+    /// renamed one whole.
+    pub fn write(path: &Path, meta: &SaveMeta, body: &str) -> Result<(), anyhow::Error> {
+        let header = ron::ser::to_string(&SaveHeader {
+            version: meta.version,
+            timestamp: meta.timestamp.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default(),
+            playtime: meta.playtime,
+        })?;
+
+        let thumbnail = match meta.thumbnail {
+            Some(ref thumbnail) => thumbnail.to_png()?,
+            None => vec![],
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let id = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let temp = path.with_extension(format!("{}.{}.tmp", process::id(), id));
+
+        let written = Self::write_file(&temp, header.as_bytes(), &thumbnail, body.as_bytes())
+            .and_then(|_| fs::rename(&temp, path));
+        if written.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+
+        Ok(written?)
+    }
+
+    fn write_file(path: &Path, header: &[u8], thumbnail: &[u8], body: &[u8]) -> Result<(), io::Error> {
+        let mut file = File::create(path)?;
+        file.write_all(SAVE_MAGIC)?;
+        Self::write_chunk(&mut file, header)?;
+        Self::write_chunk(&mut file, thumbnail)?;
+        file.write_all(body)?;
+        file.sync_all()
+    }
+
+    /// Reads only the header and thumbnail.
+    pub fn read_meta(path: &Path) -> Result<SaveMeta, anyhow::Error> {
+        Self::read_meta_from(&mut File::open(path)?)
+    }
+
+    pub fn read(path: &Path) -> Result<(SaveMeta, String), anyhow::Error> {
+        let mut file = File::open(path)?;
+        let meta = Self::read_meta_from(&mut file)?;
+
+        let mut body = String::new();
+        file.read_to_string(&mut body)?;
+
+        Ok((meta, body))
+    }
+
+    fn read_meta_from(reader: &mut impl Read) -> Result<SaveMeta, anyhow::Error> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != SAVE_MAGIC {
+            return Err(anyhow::anyhow!("Not a save file"));
+        }
+
+        let header = Self::read_chunk(reader)?;
+        let header = ron::de::from_bytes::<SaveHeader>(&header)?;
+
+        let thumbnail = Self::read_chunk(reader)?;
+        let thumbnail = if thumbnail.is_empty() {
+            None
+        } else {
+            Some(Image::from_memory(&thumbnail)?)
+        };
+
+        Ok(SaveMeta {
+            version: header.version,
+            timestamp: SystemTime::UNIX_EPOCH + header.timestamp,
+            playtime: header.playtime,
+            thumbnail,
+        })
+    }
+
+    fn write_chunk(writer: &mut impl Write, bytes: &[u8]) -> Result<(), io::Error> {
+        writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        writer.write_all(bytes)
+    }
+
+    fn read_chunk(reader: &mut impl Read) -> Result<Vec<u8>, io::Error> {
+        let mut len = [0; 4];
+        reader.read_exact(&mut len)?;
+
+        let mut bytes = vec![0; u32::from_le_bytes(len) as usize];
+        reader.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+}
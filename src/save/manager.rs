@@ -0,0 +1,321 @@
+use crate::{
+    core::prelude::*,
+    scene::Scene,
+    save::{
+        SaveFile, SaveMeta,
+        SaveEvent, LoadEvent, SaveDoneEvent, LoadDoneEvent,
+    },
+};
+#[cfg(feature = "transform")]
+use crate::transform::DespawnRecursive;
+use bevy_ecs::event::{
+    Events, ManualEventReader,
+};
+#[cfg(feature = "transform")]
+use bevy_ecs::system::Command;
+use bevy_reflect::prelude::*;
+use parking_lot::RwLock;
+use std::{
+    collections::BTreeMap,
+    env,
+    fs,
+    io,
+    path::PathBuf,
+    sync::Arc,
+    time::{
+        Duration, SystemTime,
+    },
+};
+
+pub type SaveMigration = Box<dyn Fn(String) -> Result<String, anyhow::Error> + Send + Sync>;
+
+/// Configures [`crate::save::SaveSubsystem`]. Must be inserted before the subsystem is initialized.
+#[derive(Resource, Debug, Clone)]
+pub struct SaveConfig {
+    /// Used to derive the platform save folder if `dir` is unset.
+    pub app_name: String,
+    pub dir: Option<PathBuf>,
+    /// Current schema version; older saves are migrated up to it on load.
+    pub version: u32,
+}
+
+impl SaveConfig {
+    /// `%APPDATA%` on Windows, `~/Library/Application Support` on macOS, and `$XDG_DATA_HOME` or `~/.local/share`
+    /// elsewhere, falling back to the working directory.
+    pub fn platform_dir(&self) -> PathBuf {
+        let base = if cfg!(target_os = "windows") {
+            env::var_os("APPDATA").map(PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join("Library").join("Application Support"))
+        } else {
+            env::var_os("XDG_DATA_HOME")
+                .map(PathBuf::from)
+                .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+        };
+
+        match base {
+            Some(base) => base.join(&self.app_name).join("saves"),
+            None => PathBuf::from("saves"),
+        }
+    }
+}
+
+impl Default for SaveConfig {
+    fn default() -> Self {
+        Self {
+            app_name: "avocado".to_string(),
+            dir: None,
+            version: 0,
+        }
+    }
+}
+
+/// Marks entities to be written to and replaced by save slots. Their descendants are despawned along with them on
+/// load, so should be saveable too.
+#[derive(Component, Reflect, Default, Debug, Copy, Clone)]
+#[reflect(Component)]
+pub struct Saveable;
+
+/// Handles [`SaveEvent`] and [`LoadEvent`] on the [`IoTaskPool`], replying with [`SaveDoneEvent`] and
+/// [`LoadDoneEvent`] respectively.
+#[derive(Resource)]
+pub struct SaveManager {
+    dir: PathBuf,
+    version: u32,
+    migrations: Arc<RwLock<BTreeMap<u32, SaveMigration>>>,
+
+    loaded_playtime: Duration,
+    loaded_at: Duration,
+
+    save_reader: ManualEventReader<SaveEvent>,
+    load_reader: ManualEventReader<LoadEvent>,
+}
+
+impl SaveManager {
+    pub fn save_sys(world: &mut World) {
+        world.resource_scope(|world, mut manager: Mut<Self>| {
+            let events = manager.save_reader
+                .iter(world.resource::<Events<SaveEvent>>())
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if events.is_empty() {
+                return;
+            }
+
+            let entities = world
+                .query_filtered::<Entity, With<Saveable>>()
+                .iter(world)
+                .collect::<Vec<_>>();
+
+            let registry = world.resource::<AppTypeRegistry>().clone();
+            let main_thread = world.resource::<MainThread>().clone();
+            let playtime = manager.playtime(world.resource::<Time>());
+
+            for SaveEvent { slot, thumbnail } in events {
+                let scene = Scene::from_world(world, entities.iter().copied());
+                let meta = SaveMeta {
+                    version: manager.version,
+                    timestamp: SystemTime::now(),
+                    playtime,
+                    thumbnail,
+                };
+
+                let path = match manager.slot_path(&slot) {
+                    Ok(path) => path,
+                    Err(err) => {
+                        log::error!("Couldn't save slot {}: {}", &slot, err);
+                        world.send_event(SaveDoneEvent { slot, result: Err(err.to_string()) });
+                        continue;
+                    },
+                };
+
+                let registry = registry.clone();
+                let main_thread = main_thread.clone();
+
+                IoTaskPool::get().spawn(async move {
+                    let result = scene
+                        .serialize_ron(&registry.read())
+                        .map_err(anyhow::Error::from)
+                        .and_then(|body| SaveFile::write(&path, &meta, &body))
+                        .map(|_| meta)
+                        .map_err(|err| err.to_string());
+
+                    if let Err(ref err) = result {
+                        log::error!("Couldn't save slot {}: {}", &slot, err);
+                    }
+
+                    main_thread.schedule(move |world| world.send_event(SaveDoneEvent { slot, result }));
+                }).detach();
+            }
+        });
+    }
+
+    pub fn load_sys(world: &mut World) {
+        world.resource_scope(|world, mut manager: Mut<Self>| {
+            let events = manager.load_reader
+                .iter(world.resource::<Events<LoadEvent>>())
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let registry = world.resource::<AppTypeRegistry>().clone();
+            let main_thread = world.resource::<MainThread>().clone();
+
+            for LoadEvent { slot } in events {
+                let path = match manager.slot_path(&slot) {
+                    Ok(path) => path,
+                    Err(err) => {
+                        log::error!("Couldn't load slot {}: {}", &slot, err);
+                        world.send_event(LoadDoneEvent { slot, result: Err(err.to_string()) });
+                        continue;
+                    },
+                };
+
+                let version = manager.version;
+                let migrations = Arc::clone(&manager.migrations);
+                let registry = registry.clone();
+                let main_thread = main_thread.clone();
+
+                IoTaskPool::get().spawn(async move {
+                    let result = SaveFile::read(&path).and_then(|(meta, body)| {
+                        let body = Self::migrate(&migrations.read(), meta.version, version, body)?;
+                        let scene = Scene::deserialize_ron(body.as_bytes(), &registry.read())?;
+                        Ok((meta, scene))
+                    });
+
+                    main_thread.schedule(move |world| {
+                        let result = result.and_then(|(meta, scene)| {
+                            Self::replace_saveables(world, &scene)?;
+
+                            let elapsed = world.resource::<Time>().elapsed_no_pause();
+                            let mut manager = world.resource_mut::<Self>();
+                            manager.loaded_playtime = meta.playtime;
+                            manager.loaded_at = elapsed;
+
+                            Ok(meta)
+                        }).map_err(|err| err.to_string());
+
+                        if let Err(ref err) = result {
+                            log::error!("Couldn't load slot {}: {}", &slot, err);
+                        }
+
+                        world.send_event(LoadDoneEvent { slot, result });
+                    });
+                }).detach();
+            }
+        });
+    }
+
+    pub fn new(config: SaveConfig) -> Self {
+        Self {
+            dir: config.dir.clone().unwrap_or_else(|| config.platform_dir()),
+            version: config.version,
+            migrations: Arc::default(),
+
+            loaded_playtime: Duration::ZERO,
+            loaded_at: Duration::ZERO,
+
+            save_reader: ManualEventReader::default(),
+            load_reader: ManualEventReader::default(),
+        }
+    }
+
+    #[inline]
+    pub fn dir(&self) -> &PathBuf {
+        &self.dir
+    }
+
+    #[inline]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The file of a slot. Slot names may only contain ASCII letters, digits, `_` and `-`, so they can't point
+    /// outside of the save folder.
+    pub fn slot_path(&self, slot: &str) -> Result<PathBuf, anyhow::Error> {
+        if slot.is_empty() || !slot.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(anyhow::anyhow!("Invalid save slot name {:?}", slot));
+        }
+
+        Ok(self.dir.join(format!("{}.sav", slot)))
+    }
+
+    /// Lists the names of every existing slot.
+    pub fn slots(&self) -> Result<Vec<String>, io::Error> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+
+        let mut slots = vec![];
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().map_or(false, |ext| ext == "sav") {
+                if let Some(name) = path.file_stem() {
+                    slots.push(name.to_string_lossy().into_owned());
+                }
+            }
+        }
+
+        slots.sort();
+        Ok(slots)
+    }
+
+    /// Reads the header of a slot, e.g. to display it in a load menu.
+    #[inline]
+    pub fn meta(&self, slot: &str) -> Result<SaveMeta, anyhow::Error> {
+        SaveFile::read_meta(&self.slot_path(slot)?)
+    }
+
+    /// Total playtime, counting from the playtime of the last loaded save.
+    #[inline]
+    pub fn playtime(&self, time: &Time) -> Duration {
+        self.loaded_playtime + time.elapsed_no_pause().saturating_sub(self.loaded_at)
+    }
+
+    pub fn migration(&mut self, from: u32, migration: impl Fn(String) -> Result<String, anyhow::Error> + Send + Sync + 'static) {
+        if self.migrations.write().insert(from, Box::new(migration)).is_some() {
+            panic!("Save migration from version {} is already registered", from);
+        }
+    }
+
+    fn migrate(migrations: &BTreeMap<u32, SaveMigration>, from: u32, to: u32, mut body: String) -> Result<String, anyhow::Error> {
+        if from > to {
+            return Err(anyhow::anyhow!("Save version {} is newer than {}", from, to));
+        }
+
+        for version in from..to {
+            let migration = migrations
+                .get(&version)
+                .ok_or_else(|| anyhow::anyhow!("No save migration from version {}", version))?;
+
+            body = migration(body)?;
+        }
+
+        Ok(body)
+    }
+
+    /// Spawns the scene first and only then despawns the previous saveables, so a failed load leaves them be.
+    fn replace_saveables(world: &mut World, scene: &Scene) -> Result<(), anyhow::Error> {
+        let entities = world
+            .query_filtered::<Entity, With<Saveable>>()
+            .iter(world)
+            .collect::<Vec<_>>();
+
+        scene.write_to_world(world, &mut EntityMap::default())?;
+        for entity in entities {
+            // Already gone if it was a descendant of another saveable.
+            if world.get_entity(entity).is_none() {
+                continue;
+            }
+
+            #[cfg(feature = "transform")]
+            DespawnRecursive { entity }.write(world);
+            #[cfg(not(feature = "transform"))]
+            world.despawn(entity);
+        }
+
+        Ok(())
+    }
+}
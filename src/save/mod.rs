@@ -0,0 +1,47 @@
+use crate::{
+    core::prelude::*,
+    scene::SceneSubsystem,
+};
+
+mod event;
+mod ext;
+mod file;
+mod manager;
+
+pub use event::*;
+pub use ext::*;
+pub use file::*;
+pub use manager::*;
+
+pub mod prelude {
+    pub use crate::save::{
+        SaveSubsystem, AppExt as _,
+        SaveConfig, SaveManager, SaveMeta, SaveMigration, Saveable,
+        SaveEvent, LoadEvent, SaveDoneEvent, LoadDoneEvent,
+    };
+}
+
+pub struct SaveSubsystem;
+impl Subsystem for SaveSubsystem {
+    fn init(app: &mut App) {
+        let config = app.res_or(SaveConfig::default).clone();
+        app
+            .insert_res(SaveManager::new(config))
+            .register_type::<Saveable>()
+
+            .event::<SaveEvent>()
+            .event::<LoadEvent>()
+            .event::<SaveDoneEvent>()
+            .event::<LoadDoneEvent>()
+
+            .sys(CoreStage::SysPostUpdate, SaveManager::save_sys.at_start())
+            .sys(CoreStage::SysPostUpdate, SaveManager::load_sys.at_start());
+    }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![
+            SubsystemId::of::<CoreSubsystem>(),
+            SubsystemId::of::<SceneSubsystem>(),
+        ]
+    }
+}
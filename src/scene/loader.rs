@@ -0,0 +1,36 @@
+use crate::{
+    asset::prelude::*,
+    core::prelude::*,
+    scene::Scene,
+};
+use std::{
+    borrow::Cow,
+    path::Path,
+    sync::Arc,
+};
+
+/// Loads [`Scene`]s from RON files. Component types must be registered before the scene is loaded.
+pub struct SceneLoader {
+    registry: AppTypeRegistry,
+}
+
+impl SceneLoader {
+    #[inline]
+    pub fn new(registry: AppTypeRegistry) -> Self {
+        Self { registry, }
+    }
+}
+
+impl AssetLoader for SceneLoader {
+    fn load(
+        &self,
+        reader: Arc<dyn AssetReader>, handle_path: Cow<'static, Path>,
+        _: Option<Box<dyn AssetData>>,
+        _: AssetLoadSync,
+    ) -> Result<Box<dyn AssetDyn>, anyhow::Error> {
+        let bytes = reader.read_file(&handle_path)?;
+        let scene = Scene::deserialize_ron(&bytes, &self.registry.read())?;
+
+        Ok(Box::new(scene))
+    }
+}
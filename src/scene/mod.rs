@@ -0,0 +1,48 @@
+use crate::{
+    asset::prelude::*,
+    core::prelude::*,
+};
+
+mod loader;
+mod scene;
+mod serde;
+mod spawner;
+
+pub use loader::*;
+pub use scene::*;
+pub use self::serde::*;
+pub use spawner::*;
+
+pub mod re_exports {
+    pub use ::ron;
+}
+
+pub mod prelude {
+    pub use crate::scene::{
+        SceneSubsystem,
+        Scene, SceneEntity, SceneError, SceneLoader,
+        SceneSpawner, SceneSpawnedEvent,
+    };
+}
+
+pub struct SceneSubsystem;
+impl Subsystem for SceneSubsystem {
+    fn init(app: &mut App) {
+        let registry = app.res_or(AppTypeRegistry::default).clone();
+        app
+            .init_res::<SceneSpawner>()
+            .event::<SceneSpawnedEvent>()
+
+            .asset::<Scene>()
+            .asset_loader::<Scene>(SceneLoader::new(registry))
+
+            .sys(CoreStage::PreUpdate, SceneSpawner::spawn_sys.at_start());
+    }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![
+            SubsystemId::of::<CoreSubsystem>(),
+            SubsystemId::of::<AssetSubsystem>(),
+        ]
+    }
+}
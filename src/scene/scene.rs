@@ -0,0 +1,220 @@
+use crate::{
+    core::prelude::*,
+    scene::{
+        SceneSerializer, SceneDeserializer,
+    },
+};
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_reflect::{
+    Reflect, ReflectMut, ReflectRef, TypeRegistry, TypeUuid,
+};
+use bevy_utils::HashSet;
+use serde::de::DeserializeSeed;
+use thiserror::Error;
+
+/// A set of entities with their reflected components, written to and read from RON files. Only components
+/// registered with [`App::register_type`] and reflecting [`ReflectComponent`] are kept.
+#[derive(Debug, Default, TypeUuid)]
+#[uuid = "5d5c2a07-4c1f-4a47-a4cb-4ec1b1f1d8e3"]
+pub struct Scene {
+    pub entities: Vec<SceneEntity>,
+}
+
+#[derive(Debug)]
+pub struct SceneEntity {
+    /// Scene-local identifier, the [`Entity::to_bits`] of the snapshotted entity. Remapped to a fresh entity on spawn.
+    pub entity: u64,
+    pub components: Vec<Box<dyn Reflect>>,
+}
+
+#[derive(Debug, Error)]
+pub enum SceneError {
+    #[error("Type {0} is not registered")]
+    UnregisteredType(String),
+    #[error("Type {0} doesn't reflect `Component`")]
+    UnregisteredComponent(String),
+    #[error(transparent)]
+    MapEntities(#[from] MapEntitiesError),
+    #[error(transparent)]
+    Syntax(#[from] ron::de::SpannedError),
+    #[error(transparent)]
+    Ron(#[from] ron::Error),
+}
+
+impl Scene {
+    /// Snapshots the reflectable components of `entities`.
+    pub fn from_world(world: &World, entities: impl IntoIterator<Item = Entity>) -> Self {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let entities = entities
+            .into_iter()
+            .map(|entity| SceneEntity {
+                entity: entity.to_bits(),
+                components: world
+                    .inspect_entity(entity)
+                    .iter()
+                    .filter_map(|info| {
+                        let reflect = registry.get(info.type_id()?)?.data::<ReflectComponent>()?;
+                        reflect.reflect(world, entity).map(Reflect::clone_value)
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self { entities }
+    }
+
+    /// Spawns every entity of this scene, or applies its components to the entity already mapped in
+    /// `entity_map`. Entity references inside components are remapped afterwards; references to entities outside the
+    /// scene are dropped with a warning. On error, the entities spawned so far are despawned again.
+    pub fn write_to_world(&self, world: &mut World, entity_map: &mut EntityMap) -> Result<(), SceneError> {
+        let mut spawned = vec![];
+        let result = self.write_entities(world, entity_map, &mut spawned);
+
+        if result.is_err() {
+            for (key, entity) in spawned {
+                entity_map.remove(key);
+                world.despawn(entity);
+            }
+        }
+
+        result
+    }
+
+    fn write_entities(
+        &self,
+        world: &mut World, entity_map: &mut EntityMap,
+        spawned: &mut Vec<(Entity, Entity)>,
+    ) -> Result<(), SceneError> {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        let keys = self.entities
+            .iter()
+            .map(|scene_entity| Entity::from_bits(scene_entity.entity))
+            .collect::<HashSet<_>>();
+
+        for scene_entity in &self.entities {
+            let key = Entity::from_bits(scene_entity.entity);
+            let entity = match entity_map.get(key) {
+                Ok(entity) => entity,
+                Err(..) => {
+                    let entity = world.spawn_empty().id();
+                    entity_map.insert(key, entity);
+                    spawned.push((key, entity));
+                    entity
+                },
+            };
+
+            for component in &scene_entity.components {
+                let name = component.type_name();
+                let reflect = registry
+                    .get_with_name(name)
+                    .ok_or_else(|| SceneError::UnregisteredType(name.to_string()))?
+                    .data::<ReflectComponent>()
+                    .ok_or_else(|| SceneError::UnregisteredComponent(name.to_string()))?;
+
+                let mut component = component.clone_value();
+                let mut dropped = 0;
+                if !retain_references(&mut *component, &keys, &mut dropped) {
+                    log::warn!(
+                        "Skipped {} of scene entity {}, as it refers to an entity outside the scene",
+                        name, scene_entity.entity,
+                    );
+                    continue;
+                }
+
+                if dropped > 0 {
+                    log::warn!(
+                        "Dropped {} references to entities outside the scene from {} of scene entity {}",
+                        dropped, name, scene_entity.entity,
+                    );
+                }
+
+                reflect.apply_or_insert(world, entity, &*component);
+            }
+        }
+
+        for registration in registry.iter() {
+            if let Some(map) = registration.data::<ReflectMapEntities>() {
+                map.map_entities(world, entity_map)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn serialize_ron(&self, registry: &TypeRegistry) -> Result<String, SceneError> {
+        let config = ron::ser::PrettyConfig::default()
+            .indentor("    ".to_string())
+            .struct_names(false);
+
+        Ok(ron::ser::to_string_pretty(&SceneSerializer::new(self, registry), config)?)
+    }
+
+    pub fn deserialize_ron(bytes: &[u8], registry: &TypeRegistry) -> Result<Self, SceneError> {
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        Ok(SceneDeserializer::new(registry).deserialize(&mut deserializer)?)
+    }
+}
+
+/// Removes entity references outside `keys` from lists within `value`, counting them in `dropped`. Returns `false` if
+/// such a reference is found anywhere else, as it can't be removed.
+fn retain_references(value: &mut dyn Reflect, keys: &HashSet<Entity>, dropped: &mut usize) -> bool {
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => (0..value.field_len()).all(|i| value
+            .field_at_mut(i)
+            .map_or(true, |field| retain_references(field, keys, dropped))
+        ),
+        ReflectMut::TupleStruct(value) => (0..value.field_len()).all(|i| value
+            .field_mut(i)
+            .map_or(true, |field| retain_references(field, keys, dropped))
+        ),
+        ReflectMut::Tuple(value) => (0..value.field_len()).all(|i| value
+            .field_mut(i)
+            .map_or(true, |field| retain_references(field, keys, dropped))
+        ),
+        ReflectMut::Enum(value) => (0..value.field_len()).all(|i| value
+            .field_at_mut(i)
+            .map_or(true, |field| retain_references(field, keys, dropped))
+        ),
+        ReflectMut::Array(value) => (0..value.len()).all(|i| value
+            .get_mut(i)
+            .map_or(true, |item| retain_references(item, keys, dropped))
+        ),
+        ReflectMut::List(value) => {
+            let mut items = vec![];
+            while let Some(item) = value.pop() {
+                items.push(item);
+            }
+
+            for mut item in items.into_iter().rev() {
+                if retain_references(&mut *item, keys, dropped) {
+                    value.push(item);
+                } else {
+                    *dropped += 1;
+                }
+            }
+
+            true
+        },
+        ReflectMut::Map(value) => value
+            .iter()
+            .all(|(key, value)| refers_within(key, keys) && refers_within(value, keys)),
+        ReflectMut::Value(value) => value.downcast_ref::<Entity>().map_or(true, |entity| keys.contains(entity)),
+    }
+}
+
+fn refers_within(value: &dyn Reflect, keys: &HashSet<Entity>) -> bool {
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value.iter_fields().all(|field| refers_within(field, keys)),
+        ReflectRef::TupleStruct(value) => value.iter_fields().all(|field| refers_within(field, keys)),
+        ReflectRef::Tuple(value) => value.iter_fields().all(|field| refers_within(field, keys)),
+        ReflectRef::Enum(value) => value.iter_fields().all(|field| refers_within(field.value(), keys)),
+        ReflectRef::Array(value) => value.iter().all(|item| refers_within(item, keys)),
+        ReflectRef::List(value) => value.iter().all(|item| refers_within(item, keys)),
+        ReflectRef::Map(value) => value
+            .iter()
+            .all(|(key, value)| refers_within(key, keys) && refers_within(value, keys)),
+        ReflectRef::Value(value) => value.downcast_ref::<Entity>().map_or(true, |entity| keys.contains(entity)),
+    }
+}
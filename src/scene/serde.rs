@@ -0,0 +1,204 @@
+use crate::scene::{
+    Scene, SceneEntity,
+};
+use bevy_reflect::{
+    serde::{
+        TypedReflectSerializer, TypedReflectDeserializer,
+    },
+    Reflect, TypeRegistry,
+};
+use serde::{
+    de::{
+        self,
+        DeserializeSeed, MapAccess, Visitor,
+    },
+    ser::{
+        SerializeMap, SerializeStruct,
+    },
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::fmt;
+
+const SCENE_STRUCT: &str = "Scene";
+const SCENE_ENTITIES: &str = "entities";
+
+/// Fields of [`Scene`], deserialized as identifiers as self-describing formats such as RON require.
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Entities,
+}
+
+/// Serializes a [`Scene`] as `(entities: { <id>: { "<type name>": <component>, .. }, .. })`.
+pub struct SceneSerializer<'a> {
+    scene: &'a Scene,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> SceneSerializer<'a> {
+    #[inline]
+    pub fn new(scene: &'a Scene, registry: &'a TypeRegistry) -> Self {
+        Self { scene, registry, }
+    }
+}
+
+impl Serialize for SceneSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 1)?;
+        state.serialize_field(SCENE_ENTITIES, &EntitiesSerializer {
+            entities: &self.scene.entities,
+            registry: self.registry,
+        })?;
+
+        state.end()
+    }
+}
+
+struct EntitiesSerializer<'a> {
+    entities: &'a [SceneEntity],
+    registry: &'a TypeRegistry,
+}
+
+impl Serialize for EntitiesSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_map(Some(self.entities.len()))?;
+        for entity in self.entities {
+            state.serialize_entry(&entity.entity, &ComponentsSerializer {
+                components: &entity.components,
+                registry: self.registry,
+            })?;
+        }
+
+        state.end()
+    }
+}
+
+struct ComponentsSerializer<'a> {
+    components: &'a [Box<dyn Reflect>],
+    registry: &'a TypeRegistry,
+}
+
+impl Serialize for ComponentsSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_map(Some(self.components.len()))?;
+        for component in self.components {
+            state.serialize_entry(component.type_name(), &TypedReflectSerializer::new(&**component, self.registry))?;
+        }
+
+        state.end()
+    }
+}
+
+/// Deserializes a [`Scene`] written by [`SceneSerializer`], looking component types up in the registry.
+pub struct SceneDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> SceneDeserializer<'a> {
+    #[inline]
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry, }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
+    type Value = Scene;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct(SCENE_STRUCT, &[SCENE_ENTITIES], SceneVisitor { registry: self.registry })
+    }
+}
+
+struct SceneVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
+    type Value = Scene;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("scene struct")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entities = None;
+        while let Some(key) = map.next_key::<SceneField>()? {
+            match key {
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(de::Error::duplicate_field(SCENE_ENTITIES));
+                    }
+
+                    entities = Some(map.next_value_seed(EntitiesDeserializer { registry: self.registry })?);
+                },
+            }
+        }
+
+        Ok(Scene {
+            entities: entities.ok_or_else(|| de::Error::missing_field(SCENE_ENTITIES))?,
+        })
+    }
+}
+
+struct EntitiesDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EntitiesDeserializer<'a> {
+    type Value = Vec<SceneEntity>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for EntitiesDeserializer<'a> {
+    type Value = Vec<SceneEntity>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("map of entities")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entities = vec![];
+        while let Some(entity) = map.next_key::<u64>()? {
+            let components = map.next_value_seed(ComponentsDeserializer { registry: self.registry })?;
+            entities.push(SceneEntity { entity, components, });
+        }
+
+        Ok(entities)
+    }
+}
+
+struct ComponentsDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ComponentsDeserializer<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for ComponentsDeserializer<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("map of components")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut components = vec![];
+        while let Some(name) = map.next_key::<String>()? {
+            let registration = self.registry
+                .get_with_name(&name)
+                .ok_or_else(|| de::Error::custom(format!("Type {} is not registered", &name)))?;
+
+            components.push(map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?);
+        }
+
+        Ok(components)
+    }
+}
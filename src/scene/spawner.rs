@@ -0,0 +1,57 @@
+use crate::{
+    asset::prelude::*,
+    core::prelude::*,
+    scene::Scene,
+};
+
+/// Sent once a queued scene has been spawned.
+#[derive(Debug, Clone)]
+pub struct SceneSpawnedEvent {
+    pub scene: Handle<Scene>,
+    pub entities: Vec<Entity>,
+}
+
+/// Spawns queued scenes as soon as they're loaded, at the start of [`CoreStage::PreUpdate`].
+#[derive(Resource, Default)]
+pub struct SceneSpawner {
+    queue: Vec<Handle<Scene>>,
+}
+
+impl SceneSpawner {
+    pub fn spawn_sys(world: &mut World) {
+        world.resource_scope(|world, mut spawner: Mut<Self>| {
+            if spawner.queue.is_empty() {
+                return;
+            }
+
+            world.resource_scope(|world, scenes: Mut<Assets<Scene>>| {
+                spawner.queue.retain(|handle| {
+                    if let AssetState::Errored(ref err) = world.resource::<AssetServer>().state(handle) {
+                        log::error!("Couldn't load scene {:?}: {}", handle.path(), err);
+                        return false;
+                    }
+
+                    let Some(scene) = scenes.get(handle) else { return true };
+
+                    let mut entity_map = EntityMap::default();
+                    match scene.write_to_world(world, &mut entity_map) {
+                        Ok(_) => world.send_event(SceneSpawnedEvent {
+                            scene: handle.clone(),
+                            entities: entity_map.values().collect(),
+                        }),
+                        Err(err) => log::error!("Couldn't spawn scene {:?}: {}", handle.path(), err),
+                    }
+
+                    false
+                });
+            });
+        });
+    }
+
+    /// Queues `scene` to be spawned once it's loaded. Spawning the same scene multiple times creates separate
+    /// copies of its entities.
+    #[inline]
+    pub fn spawn(&mut self, scene: Handle<Scene>) {
+        self.queue.push(scene);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::core::prelude::*;
+use bevy_reflect::prelude::*;
+use derive_more::*;
+use smallvec::SmallVec;
+
+#[derive(Component, Reflect, Debug, Copy, Clone, PartialEq, Eq, Deref)]
+#[reflect(Component, MapEntities, PartialEq)]
+pub struct Parent(pub(crate) Entity);
+
+impl FromWorld for Parent {
+    /// Only meant for reflection, which overwrites the placeholder entity right away.
+    #[inline]
+    fn from_world(_: &mut World) -> Self {
+        Self(Entity::from_raw(u32::MAX))
+    }
+}
+
+impl MapEntities for Parent {
+    #[inline]
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        self.0 = entity_map.get(self.0)?;
+        Ok(())
+    }
+}
+
+impl Parent {
+    #[inline]
+    pub fn get(&self) -> Entity {
+        self.0
+    }
+}
+
+#[derive(Component, Reflect, Debug, Default, Clone, Deref)]
+#[reflect(Component, MapEntities)]
+pub struct Children(pub(crate) SmallVec<[Entity; 8]>);
+
+impl MapEntities for Children {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        for entity in &mut self.0 {
+            *entity = entity_map.get(*entity)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct SetParent {
+    pub child: Entity,
+    pub parent: Entity,
+}
+
+impl Command for SetParent {
+    fn write(self, world: &mut World) {
+        // The parent must not be the child itself or one of its descendants, or the hierarchy would loop.
+        let mut ancestor = Some(self.parent);
+        while let Some(entity) = ancestor {
+            if entity == self.child {
+                log::warn!(
+                    "Couldn't set parent of {:?}; parent {:?} is the child itself or one of its descendants",
+                    self.child, self.parent,
+                );
+                return;
+            }
+
+            ancestor = world.get::<Parent>(entity).map(Parent::get);
+        }
+
+        RemoveParent { child: self.child }.write(world);
+        if world.get_entity(self.parent).is_none() {
+            log::warn!("Couldn't set parent of {:?}; parent {:?} doesn't exist", self.child, self.parent);
+            return;
+        }
+
+        let Some(mut child) = world.get_entity_mut(self.child) else { return };
+        child.insert(Parent(self.parent));
+
+        let mut parent = world.entity_mut(self.parent);
+        match parent.get_mut::<Children>() {
+            Some(mut children) => children.0.push(self.child),
+            None => {
+                parent.insert(Children(SmallVec::from_slice(&[self.child])));
+            },
+        }
+    }
+}
+
+pub struct RemoveParent {
+    pub child: Entity,
+}
+
+impl Command for RemoveParent {
+    fn write(self, world: &mut World) {
+        let Some(mut child) = world.get_entity_mut(self.child) else { return };
+        let Some(Parent(parent)) = child.remove::<Parent>() else { return };
+
+        if let Some(mut parent) = world.get_entity_mut(parent) {
+            if let Some(mut children) = parent.get_mut::<Children>() {
+                children.0.retain(|entity| *entity != self.child);
+            }
+        }
+    }
+}
+
+/// Despawns an entity along with all of its descendants.
+pub struct DespawnRecursive {
+    pub entity: Entity,
+}
+
+impl Command for DespawnRecursive {
+    fn write(self, world: &mut World) {
+        fn despawn(world: &mut World, entity: Entity) {
+            let Some(mut entity_mut) = world.get_entity_mut(entity) else { return };
+            if let Some(children) = entity_mut.remove::<Children>() {
+                for child in children.0 {
+                    despawn(world, child);
+                }
+            }
+
+            world.despawn(entity);
+        }
+
+        RemoveParent { child: self.entity }.write(world);
+        despawn(world, self.entity);
+    }
+}
+
+pub trait EntityCommandsExt {
+    fn set_parent(&mut self, parent: Entity) -> &mut Self;
+    fn remove_parent(&mut self) -> &mut Self;
+    fn despawn_recursive(self);
+}
+
+impl EntityCommandsExt for EntityCommands<'_, '_, '_> {
+    fn set_parent(&mut self, parent: Entity) -> &mut Self {
+        let child = self.id();
+        self.commands().add(SetParent { child, parent });
+        self
+    }
+
+    fn remove_parent(&mut self) -> &mut Self {
+        let child = self.id();
+        self.commands().add(RemoveParent { child });
+        self
+    }
+
+    fn despawn_recursive(mut self) {
+        let entity = self.id();
+        self.commands().add(DespawnRecursive { entity });
+    }
+}
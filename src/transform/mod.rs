@@ -0,0 +1,43 @@
+use crate::core::prelude::*;
+use bevy_math::{
+    Affine2, Mat2, Vec2,
+};
+use smallvec::SmallVec;
+
+mod hierarchy;
+mod transform;
+
+pub use hierarchy::*;
+pub use transform::*;
+
+pub mod prelude {
+    pub use crate::transform::{
+        TransformSubsystem,
+        Transform, GlobalTransform, TransformBundle,
+        Parent, Children, EntityCommandsExt as _,
+    };
+}
+
+pub struct TransformSubsystem;
+impl Subsystem for TransformSubsystem {
+    fn init(app: &mut App) {
+        app.sys(CoreStage::PostUpdate, GlobalTransform::propagate_sys
+            .label(CoreLabel::ComputeTransform)
+        )
+        .register_type::<Transform>()
+        .register_type::<GlobalTransform>()
+        .register_type::<Parent>()
+        .register_type::<Children>()
+        // Field types, looked up by scene deserialization.
+        .register_type::<Vec2>()
+        .register_type::<Mat2>()
+        .register_type::<Affine2>()
+        .register_type::<Entity>()
+        .register_type::<SmallVec<[Entity; 8]>>()
+        .validate_requires::<Transform, GlobalTransform>();
+    }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![SubsystemId::of::<CoreSubsystem>()]
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{
+    core::prelude::*,
+    transform::{
+        Parent, Children,
+    },
+};
+use bevy_reflect::prelude::*;
+use bevy_math::{
+    Affine2, Mat4,
+    Vec2, Vec4,
+};
+
+/// An entity's 2D transform, relative to its [`Parent`] if it has one.
+#[derive(Component, Reflect, Debug, Copy, Clone, PartialEq)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Transform {
+    pub translation: Vec2,
+    /// Depth, added onto the parent's.
+    pub z: f32,
+    /// Rotation in degrees, counter-clockwise.
+    pub rotation: f32,
+    pub scale: Vec2,
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self {
+        translation: Vec2::ZERO,
+        z: 0.,
+        rotation: 0.,
+        scale: Vec2::ONE,
+    };
+
+    #[inline]
+    pub fn from_xy(x: f32, y: f32) -> Self {
+        Self::from_translation(Vec2 { x, y })
+    }
+
+    #[inline]
+    pub fn from_translation(translation: Vec2) -> Self {
+        Self { translation, ..Self::IDENTITY }
+    }
+
+    #[inline]
+    pub fn with_z(mut self, z: f32) -> Self {
+        self.z = z;
+        self
+    }
+
+    #[inline]
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    #[inline]
+    pub fn with_scale(mut self, scale: Vec2) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    #[inline]
+    pub fn compute_affine(&self) -> Affine2 {
+        Affine2::from_scale_angle_translation(self.scale, self.rotation.to_radians(), self.translation)
+    }
+}
+
+impl Default for Transform {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// An entity's absolute transform, computed from its [`Transform`] and its ancestors' in
+/// [`CoreLabel::ComputeTransform`]. Don't modify this directly.
+#[derive(Component, Reflect, Debug, Copy, Clone, PartialEq)]
+#[reflect(Component, Default, PartialEq)]
+pub struct GlobalTransform {
+    affine: Affine2,
+    z: f32,
+}
+
+impl GlobalTransform {
+    pub const IDENTITY: Self = Self {
+        affine: Affine2::IDENTITY,
+        z: 0.,
+    };
+
+    pub fn propagate_sys(
+        roots: Query<(Entity, &Transform, Option<&Children>), Without<Parent>>,
+        transforms: Query<(&Transform, Option<&Children>), With<Parent>>,
+        mut globals: Query<&mut GlobalTransform>,
+    ) {
+        fn propagate(
+            parent: &GlobalTransform, children: &Children,
+            transforms: &Query<(&Transform, Option<&Children>), With<Parent>>,
+            globals: &mut Query<&mut GlobalTransform>,
+        ) {
+            for child in children.iter() {
+                let Ok((transform, children)) = transforms.get(*child) else { continue };
+
+                let global = parent.mul_transform(transform);
+                // Only assigned if it differs, so `Changed<GlobalTransform>` stays meaningful.
+                match globals.get_mut(*child) {
+                    Ok(mut child_global) if *child_global != global => *child_global = global,
+                    _ => {},
+                }
+
+                if let Some(children) = children {
+                    propagate(&global, children, transforms, globals);
+                }
+            }
+        }
+
+        for (entity, transform, children) in &roots {
+            let global = GlobalTransform::from(*transform);
+            match globals.get_mut(entity) {
+                Ok(mut root_global) if *root_global != global => *root_global = global,
+                _ => {},
+            }
+
+            if let Some(children) = children {
+                propagate(&global, children, &transforms, &mut globals);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn mul_transform(&self, transform: &Transform) -> Self {
+        Self {
+            affine: self.affine * transform.compute_affine(),
+            z: self.z + transform.z,
+        }
+    }
+
+    #[inline]
+    pub fn affine(&self) -> Affine2 {
+        self.affine
+    }
+
+    #[inline]
+    pub fn translation(&self) -> Vec2 {
+        self.affine.translation
+    }
+
+    #[inline]
+    pub fn z(&self) -> f32 {
+        self.z
+    }
+
+    /// Rotation in degrees, counter-clockwise.
+    #[inline]
+    pub fn rotation(&self) -> f32 {
+        let x_axis = self.affine.matrix2.x_axis;
+        x_axis.y.atan2(x_axis.x).to_degrees()
+    }
+
+    #[inline]
+    pub fn scale(&self) -> Vec2 {
+        Vec2 {
+            x: self.affine.matrix2.x_axis.length(),
+            y: self.affine.matrix2.y_axis.length(),
+        }
+    }
+
+    #[inline]
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        self.affine.transform_point2(point)
+    }
+
+    pub fn compute_matrix(&self) -> Mat4 {
+        let x_axis = self.affine.matrix2.x_axis;
+        let y_axis = self.affine.matrix2.y_axis;
+        let translation = self.affine.translation;
+
+        Mat4::from_cols(
+            Vec4::new(x_axis.x, x_axis.y, 0., 0.),
+            Vec4::new(y_axis.x, y_axis.y, 0., 0.),
+            Vec4::Z,
+            Vec4::new(translation.x, translation.y, self.z, 1.),
+        )
+    }
+}
+
+impl Default for GlobalTransform {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Transform> for GlobalTransform {
+    #[inline]
+    fn from(transform: Transform) -> Self {
+        Self {
+            affine: transform.compute_affine(),
+            z: transform.z,
+        }
+    }
+}
+
+#[derive(Bundle, Default, Debug, Copy, Clone)]
+pub struct TransformBundle {
+    pub local: Transform,
+    pub global: GlobalTransform,
+}
+
+impl From<Transform> for TransformBundle {
+    #[inline]
+    fn from(local: Transform) -> Self {
+        Self {
+            local,
+            global: local.into(),
+        }
+    }
+}
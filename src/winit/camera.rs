@@ -1,16 +1,18 @@
 use crate::{
     core::prelude::*,
-    winit::Renderer,
+    transform::prelude::*,
+    winit::{
+        Renderer, SurfaceConfig,
+    },
 };
 use bevy_math::{
-    Vec3, Mat4,
+    Vec2, Vec3, Mat4,
 };
 use wgpu::util::DeviceExt as _;
 
+/// A camera, positioned by the entity's [`GlobalTransform`].
 #[derive(Component)]
 pub struct Camera {
-    /// The camera's position.
-    pub position: Vec3,
     pub near: f32,
     pub far: f32,
     pub proj: CameraProj,
@@ -18,12 +20,14 @@ pub struct Camera {
 
 #[derive(Debug, Copy, Clone)]
 pub enum CameraProj {
+    /// Orthographic projection. The transform's rotation and scale apply to the view, but its `z` doesn't.
     Orthographic {
         /// Orthographic viewport width.
         width: f32,
         /// Orthographic viewport height.
         height: f32,
     },
+    /// Perspective projection, looking from the transform's translation and `z`.
     Perspective {
         /// The camera's "look" angle.
         target: Vec3,
@@ -87,19 +91,40 @@ impl FromWorld for GlobalCamera {
 }
 
 impl GlobalCamera {
+    /// Whether [`GlobalCamera::entity`] is a positioned [`Camera`] whose projection can show anything.
+    pub fn is_valid(world: &World) -> bool {
+        let Some(global_camera) = world.get_resource::<Self>() else { return false };
+        let Some(entity) = world.get_entity(global_camera.entity) else { return false };
+        let Some(camera) = entity.get::<Camera>() else { return false };
+
+        entity.contains::<GlobalTransform>() && camera.far > camera.near && match camera.proj {
+            CameraProj::Orthographic { width, height } => width > 0. && height > 0.,
+            CameraProj::Perspective { aspect, fov, .. } => aspect > 0. && fov > 0. && fov < 180.,
+        }
+    }
+
     pub fn update_sys(
         mut global_camera: ResMut<GlobalCamera>, renderer: Res<Renderer>,
-        query: Query<&Camera, Changed<Camera>>
+        query: Query<(&Camera, &GlobalTransform), Or<(Changed<Camera>, Changed<GlobalTransform>)>>
     ) {
-        if let Ok(camera) = query.get(global_camera.entity) {
+        if let Ok((camera, transform)) = query.get(global_camera.entity) {
             global_camera.proj = Some(match camera.proj {
-                CameraProj::Orthographic { width, height } => Mat4::orthographic_rh(
-                    camera.position.x - width / 2., camera.position.x + width / 2.,
-                    camera.position.y - height / 2., camera.position.y + height / 2.,
-                    camera.near, camera.far,
-                ),
+                CameraProj::Orthographic { width, height } => {
+                    // Orthographic views ignore depth.
+                    let mut view = transform.compute_matrix();
+                    view.w_axis.z = 0.;
+
+                    let proj = Mat4::orthographic_rh(
+                        -width / 2., width / 2.,
+                        -height / 2., height / 2.,
+                        camera.near, camera.far,
+                    );
+
+                    proj * view.inverse()
+                },
                 CameraProj::Perspective { target, up, aspect, fov, } => {
-                    let view = Mat4::look_at_rh(camera.position, target, up.normalize());
+                    let position = transform.translation().extend(transform.z());
+                    let view = Mat4::look_at_rh(position, target, up.normalize());
                     let proj = Mat4::perspective_rh(
                         fov.to_radians(), aspect,
                         camera.near, camera.far,
@@ -115,4 +140,37 @@ impl GlobalCamera {
             );
         }
     }
+
+    /// Converts a position in physical pixels from the top-left corner of a `viewport`-sized area to world
+    /// coordinates on the `z = 0` plane. Returns `None` if there's no projection yet or the view is parallel to
+    /// that plane.
+    pub fn viewport_to_world(&self, position: Vec2, viewport: Vec2) -> Option<Vec2> {
+        let proj = self.proj?;
+        if viewport.x <= 0. || viewport.y <= 0. {
+            return None;
+        }
+
+        let ndc = Vec2::new(
+            position.x / viewport.x * 2. - 1.,
+            1. - position.y / viewport.y * 2.,
+        );
+
+        let inverse = proj.inverse();
+        let near = inverse.project_point3(ndc.extend(0.));
+        let far = inverse.project_point3(ndc.extend(1.));
+
+        // Orthographic views ignore depth, so both points already lie on the plane.
+        if near.z == far.z {
+            return Some(near.truncate());
+        }
+
+        let t = near.z / (near.z - far.z);
+        t.is_finite().then(|| near.lerp(far, t).truncate())
+    }
+
+    /// Converts a cursor position, e.g. [`Mouse::position`](crate::input::Mouse::position), to world coordinates.
+    #[inline]
+    pub fn cursor_to_world(&self, cursor: Vec2, surface: &SurfaceConfig) -> Option<Vec2> {
+        self.viewport_to_world(cursor, Vec2::new(surface.size.width as f32, surface.size.height as f32))
+    }
 }
@@ -1,7 +1,11 @@
 use crate::{
     core::prelude::*,
     graphics::prelude::*,
-    winit::WinitWindow,
+    winit::{
+        WinitWindow,
+        SurfaceConfig, Renderer,
+        WindowResizedEvent,
+    },
 };
 use derive_more::*;
 use thiserror::Error;
@@ -12,6 +16,7 @@ use winit::{
     },
     error::OsError,
     event_loop::EventLoopWindowTarget,
+    monitor::MonitorHandle,
     window::{
         Window, WindowBuilder,
         Fullscreen,
@@ -55,30 +60,119 @@ impl WindowConfig {
         window.set_visible(config.visible);
     }
 
+    /// Applies runtime changes of the title, fullscreen, maximization and vsync, and writes them back to
+    /// [`Settings`] along with the window size.
+    pub fn update_sys(
+        window: NonSend<WinitWindow>, config: Res<WindowConfig>, mut settings: ResMut<Settings>,
+        mut surface: ResMut<SurfaceConfig>, renderer: Res<Renderer>,
+        mut resized: EventReader<WindowResizedEvent>,
+    ) {
+        if config.is_changed() && !config.is_added() {
+            window.set_title(&config.title);
+
+            let fullscreen_applied = matches!(
+                (window.fullscreen(), config.fullscreen),
+                (None, None) |
+                (Some(Fullscreen::Exclusive(..)), Some(false)) |
+                (Some(Fullscreen::Borderless(..)), Some(true))
+            );
+
+            if !fullscreen_applied {
+                let monitor = window.current_monitor().or_else(|| window.available_monitors().next());
+                window.set_fullscreen(config.fullscreen_on(monitor));
+            }
+
+            if window.is_maximized() != config.maximized {
+                window.set_maximized(config.maximized);
+            }
+
+            let present_mode = config.present_mode();
+            if surface.config.present_mode != present_mode {
+                surface.config.present_mode = present_mode;
+                surface.surface.configure(&renderer.device, &surface.config);
+            }
+
+            config.write_settings(&mut settings);
+        }
+
+        if let Some(size) = resized.iter().last() {
+            if window.fullscreen().is_none() && !window.is_maximized() {
+                let size = size.to_logical::<f64>(window.scale_factor());
+                settings.set("window.width", size.width);
+                settings.set("window.height", size.height);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn present_mode(&self) -> wgpu::PresentMode {
+        if self.vsync {
+            wgpu::PresentMode::AutoVsync
+        } else {
+            wgpu::PresentMode::AutoNoVsync
+        }
+    }
+
+    /// Reads `window.{width, height, title, resizable, fullscreen, maximized, decorations, always_on_top, vsync}`,
+    /// where `fullscreen` is one of `windowed`, `exclusive` or `borderless`.
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        if let (Some(width), Some(height)) = (settings.get::<f64>("window.width"), settings.get::<f64>("window.height")) {
+            self.size = Some(Size::Logical(LogicalSize { width, height }));
+        }
+
+        if let Some(fullscreen) = settings.get::<String>("window.fullscreen") {
+            match fullscreen.as_str() {
+                "windowed" => self.fullscreen = None,
+                "exclusive" => self.fullscreen = Some(false),
+                "borderless" => self.fullscreen = Some(true),
+                _ => log::warn!("Invalid fullscreen mode `{}`", fullscreen),
+            }
+        }
+
+        if let Some(title) = settings.get("window.title") { self.title = title; }
+        if let Some(resizable) = settings.get("window.resizable") { self.resizable = resizable; }
+        if let Some(maximized) = settings.get("window.maximized") { self.maximized = maximized; }
+        if let Some(decorations) = settings.get("window.decorations") { self.decorations = decorations; }
+        if let Some(always_on_top) = settings.get("window.always_on_top") { self.always_on_top = always_on_top; }
+        if let Some(vsync) = settings.get("window.vsync") { self.vsync = vsync; }
+    }
+
+    pub fn write_settings(&self, settings: &mut Settings) {
+        settings.set("window.fullscreen", match self.fullscreen {
+            None => "windowed",
+            Some(false) => "exclusive",
+            Some(true) => "borderless",
+        });
+
+        settings.set("window.maximized", self.maximized);
+        settings.set("window.vsync", self.vsync);
+    }
+
+    /// The configured fullscreen mode on `monitor`, falling back to borderless if it has no video mode.
+    pub fn fullscreen_on(&self, monitor: Option<MonitorHandle>) -> Option<Fullscreen> {
+        let borderless = self.fullscreen?;
+        match monitor.as_ref().and_then(|monitor| monitor.video_modes().find(|_| true)) {
+            Some(mode) if !borderless => Some(Fullscreen::Exclusive(mode)),
+            mode => {
+                if mode.is_none() && !borderless {
+                    log::warn!("No available video mode found; using borderless fullscreen instead.");
+                }
+
+                Some(Fullscreen::Borderless(monitor))
+            },
+        }
+    }
+
     pub fn create<T>(&self, event_loop: &EventLoopWindowTarget<T>) -> WindowCreationResult {
         let monitor = event_loop.available_monitors().find(|_| true).ok_or(WindowCreationError::NoMonitor)?;
-        let mode = monitor.video_modes().find(|_| true);
 
         let mut builder = WindowBuilder::new();
         builder = if let Some(size) = self.size { builder.with_inner_size(size) } else { builder };
         builder = if let Some(min_size) = self.min_size { builder.with_min_inner_size(min_size) } else { builder };
         builder = if let Some(max_size) = self.max_size { builder.with_max_inner_size(max_size) } else { builder };
 
-        builder = if let Some(borderless) = self.fullscreen {
-            if borderless || mode.is_none() {
-                if !borderless {
-                    log::warn!("No available video mode found; using borderless fullscreen instead.");
-                }
-
-                builder.with_fullscreen(Some(Fullscreen::Borderless(Some(monitor.clone()))))
-            } else {
-                builder.with_fullscreen(Some(Fullscreen::Exclusive(mode.unwrap())))
-            }
-        } else {
-            builder
-        };
-
         match builder
+            .with_fullscreen(self.fullscreen_on(Some(monitor.clone())))
             .with_title(self.title.clone())
             .with_resizable(self.resizable)
             .with_maximized(self.maximized)
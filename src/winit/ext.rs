@@ -3,7 +3,10 @@ use crate::{
     input::prelude::*,
 };
 
-use winit::event::VirtualKeyCode as Key;
+use winit::event::{
+    VirtualKeyCode as Key,
+    MouseButton as WinitMouseButton,
+};
 
 pub trait ColorExt: Sized {
     fn from_wgpu(other: wgpu::Color) -> Self;
@@ -27,8 +30,26 @@ impl ColorExt for Color {
     }
 }
 
+pub trait MouseButtonExt: Sized {
+    fn from_winit(button: WinitMouseButton) -> Self;
+}
+
+impl MouseButtonExt for MouseButton {
+    #[inline]
+    fn from_winit(button: WinitMouseButton) -> Self {
+        match button {
+            WinitMouseButton::Left => Self::Left,
+            WinitMouseButton::Right => Self::Right,
+            WinitMouseButton::Middle => Self::Middle,
+            WinitMouseButton::Other(button) => Self::Other(button),
+        }
+    }
+}
+
 pub trait KeyCodeExt: Sized {
     fn from_vkey(key: Key) -> Option<Self>;
+    /// The key at the position of `scancode` on a US QWERTY layout.
+    fn from_scancode(scancode: u32) -> Option<Self>;
 }
 
 impl KeyCodeExt for KeyCode {
@@ -73,7 +94,217 @@ impl KeyCodeExt for KeyCode {
             Key::Y => Y,
             Key::Z => Z,
 
+            Key::F1 => F1,
+            Key::F2 => F2,
+            Key::F3 => F3,
+            Key::F4 => F4,
+            Key::F5 => F5,
+            Key::F6 => F6,
+            Key::F7 => F7,
+            Key::F8 => F8,
+            Key::F9 => F9,
+            Key::F10 => F10,
+            Key::F11 => F11,
+            Key::F12 => F12,
+            Key::F13 => F13,
+            Key::F14 => F14,
+            Key::F15 => F15,
+            Key::F16 => F16,
+            Key::F17 => F17,
+            Key::F18 => F18,
+            Key::F19 => F19,
+            Key::F20 => F20,
+            Key::F21 => F21,
+            Key::F22 => F22,
+            Key::F23 => F23,
+            Key::F24 => F24,
+
+            Key::Escape => Escape,
+            Key::Tab => Tab,
+            Key::Capital => CapsLock,
+            Key::Space => Space,
+            Key::Return => Enter,
+            Key::Back => Backspace,
+            Key::Snapshot => PrintScreen,
+            Key::Scroll => ScrollLock,
+            Key::Pause => Pause,
+            Key::Insert => Insert,
+            Key::Delete => Delete,
+            Key::Home => Home,
+            Key::End => End,
+            Key::PageUp => PageUp,
+            Key::PageDown => PageDown,
+            Key::Left => Left,
+            Key::Up => Up,
+            Key::Right => Right,
+            Key::Down => Down,
+
+            Key::LShift => LShift,
+            Key::RShift => RShift,
+            Key::LControl => LCtrl,
+            Key::RControl => RCtrl,
+            Key::LAlt => LAlt,
+            Key::RAlt => RAlt,
+            Key::LWin => LLogo,
+            Key::RWin => RLogo,
+            Key::Apps => Menu,
+            Key::Compose => Compose,
+
+            Key::Numlock => NumLock,
+            Key::Numpad0 => Numpad0,
+            Key::Numpad1 => Numpad1,
+            Key::Numpad2 => Numpad2,
+            Key::Numpad3 => Numpad3,
+            Key::Numpad4 => Numpad4,
+            Key::Numpad5 => Numpad5,
+            Key::Numpad6 => Numpad6,
+            Key::Numpad7 => Numpad7,
+            Key::Numpad8 => Numpad8,
+            Key::Numpad9 => Numpad9,
+            Key::NumpadAdd => NumpadAdd,
+            Key::NumpadSubtract => NumpadSubtract,
+            Key::NumpadMultiply => NumpadMultiply,
+            Key::NumpadDivide => NumpadDivide,
+            Key::NumpadDecimal => NumpadDecimal,
+            Key::NumpadComma => NumpadComma,
+            Key::NumpadEnter => NumpadEnter,
+            Key::NumpadEquals => NumpadEquals,
+
+            Key::Grave => Grave,
+            Key::Minus => Minus,
+            Key::Equals => Equals,
+            Key::LBracket => LBracket,
+            Key::RBracket => RBracket,
+            Key::Backslash => Backslash,
+            Key::Semicolon => Semicolon,
+            Key::Apostrophe => Apostrophe,
+            Key::Comma => Comma,
+            Key::Period => Period,
+            Key::Slash => Slash,
+            Key::Asterisk => Asterisk,
+            Key::At => At,
+            Key::Caret => Caret,
+            Key::Colon => Colon,
+            Key::Plus => Plus,
+            Key::Underline => Underline,
+
+            Key::AbntC1 => AbntC1,
+            Key::AbntC2 => AbntC2,
+            Key::Ax => Ax,
+            Key::Convert => Convert,
+            Key::Kana => Kana,
+            Key::Kanji => Kanji,
+            Key::NoConvert => NoConvert,
+            Key::OEM102 => Oem102,
+            Key::Yen => Yen,
+
+            Key::Mute => Mute,
+            Key::VolumeDown => VolumeDown,
+            Key::VolumeUp => VolumeUp,
+            Key::PlayPause => PlayPause,
+            Key::MediaStop => MediaStop,
+            Key::NextTrack => NextTrack,
+            Key::PrevTrack => PrevTrack,
+            Key::MediaSelect => MediaSelect,
+
+            Key::Power => Power,
+            Key::Sleep => Sleep,
+            Key::Wake => Wake,
+            Key::Sysrq => SysRq,
+            Key::Calculator => Calculator,
+            Key::Mail => Mail,
+            Key::MyComputer => MyComputer,
+            Key::NavigateForward => NavigateForward,
+            Key::NavigateBackward => NavigateBackward,
+            Key::WebBack => WebBack,
+            Key::WebForward => WebForward,
+            Key::WebFavorites => WebFavorites,
+            Key::WebHome => WebHome,
+            Key::WebRefresh => WebRefresh,
+            Key::WebSearch => WebSearch,
+            Key::WebStop => WebStop,
+            Key::Copy => Copy,
+            Key::Cut => Cut,
+            Key::Paste => Paste,
+            Key::Stop => Stop,
+            Key::Unlabeled => Unlabeled,
+        })
+    }
+
+    /// macOS reports virtual key codes, which are layout-independent for these keys.
+    #[cfg(target_os = "macos")]
+    fn from_scancode(scancode: u32) -> Option<Self> {
+        use KeyCode::*;
+        Some(match scancode {
+            0x00 => A, 0x01 => S, 0x02 => D, 0x03 => F, 0x04 => H, 0x05 => G, 0x06 => Z, 0x07 => X,
+            0x08 => C, 0x09 => V, 0x0B => B, 0x0C => Q, 0x0D => W, 0x0E => E, 0x0F => R, 0x10 => Y,
+            0x11 => T, 0x12 => N1, 0x13 => N2, 0x14 => N3, 0x15 => N4, 0x16 => N6, 0x17 => N5, 0x18 => Equals,
+            0x19 => N9, 0x1A => N7, 0x1B => Minus, 0x1C => N8, 0x1D => N0, 0x1E => RBracket, 0x1F => O, 0x20 => U,
+            0x21 => LBracket, 0x22 => I, 0x23 => P, 0x24 => Enter, 0x25 => L, 0x26 => J, 0x27 => Apostrophe, 0x28 => K,
+            0x29 => Semicolon, 0x2A => Backslash, 0x2B => Comma, 0x2C => Slash, 0x2D => N, 0x2E => M, 0x2F => Period,
+            0x30 => Tab, 0x31 => Space, 0x32 => Grave, 0x33 => Backspace, 0x35 => Escape,
+            0x36 => RLogo, 0x37 => LLogo, 0x38 => LShift, 0x39 => CapsLock, 0x3A => LAlt, 0x3B => LCtrl,
+            0x3C => RShift, 0x3D => RAlt, 0x3E => RCtrl,
+
+            0x41 => NumpadDecimal, 0x43 => NumpadMultiply, 0x45 => NumpadAdd, 0x47 => NumLock,
+            0x4B => NumpadDivide, 0x4C => NumpadEnter, 0x4E => NumpadSubtract, 0x51 => NumpadEquals,
+            0x52 => Numpad0, 0x53 => Numpad1, 0x54 => Numpad2, 0x55 => Numpad3, 0x56 => Numpad4,
+            0x57 => Numpad5, 0x58 => Numpad6, 0x59 => Numpad7, 0x5B => Numpad8, 0x5C => Numpad9,
+            0x48 => VolumeUp, 0x49 => VolumeDown, 0x4A => Mute,
+
+            0x7A => F1, 0x78 => F2, 0x63 => F3, 0x76 => F4, 0x60 => F5, 0x61 => F6, 0x62 => F7, 0x64 => F8,
+            0x65 => F9, 0x6D => F10, 0x67 => F11, 0x6F => F12, 0x69 => F13, 0x6B => F14, 0x71 => F15, 0x6A => F16,
+            0x40 => F17, 0x4F => F18, 0x50 => F19, 0x5A => F20,
+
+            0x72 => Insert, 0x73 => Home, 0x74 => PageUp, 0x75 => Delete, 0x77 => End, 0x79 => PageDown,
+            0x7B => Left, 0x7C => Right, 0x7D => Down, 0x7E => Up,
+
             _ => return None,
         })
     }
+
+    /// Windows reports PC set 1 scancodes, with `0xE000` added for extended keys; Linux reports evdev codes,
+    /// which match set 1 for non-extended keys.
+    #[cfg(not(target_os = "macos"))]
+    fn from_scancode(scancode: u32) -> Option<Self> {
+        use KeyCode::*;
+        Some(match scancode {
+            0x01 => Escape, 0x02 => N1, 0x03 => N2, 0x04 => N3, 0x05 => N4, 0x06 => N5, 0x07 => N6, 0x08 => N7,
+            0x09 => N8, 0x0A => N9, 0x0B => N0, 0x0C => Minus, 0x0D => Equals, 0x0E => Backspace, 0x0F => Tab,
+            0x10 => Q, 0x11 => W, 0x12 => E, 0x13 => R, 0x14 => T, 0x15 => Y, 0x16 => U, 0x17 => I, 0x18 => O,
+            0x19 => P, 0x1A => LBracket, 0x1B => RBracket, 0x1C => Enter, 0x1D => LCtrl,
+            0x1E => A, 0x1F => S, 0x20 => D, 0x21 => F, 0x22 => G, 0x23 => H, 0x24 => J, 0x25 => K, 0x26 => L,
+            0x27 => Semicolon, 0x28 => Apostrophe, 0x29 => Grave, 0x2A => LShift, 0x2B => Backslash,
+            0x2C => Z, 0x2D => X, 0x2E => C, 0x2F => V, 0x30 => B, 0x31 => N, 0x32 => M,
+            0x33 => Comma, 0x34 => Period, 0x35 => Slash, 0x36 => RShift, 0x37 => NumpadMultiply, 0x38 => LAlt,
+            0x39 => Space, 0x3A => CapsLock,
+
+            0x3B => F1, 0x3C => F2, 0x3D => F3, 0x3E => F4, 0x3F => F5, 0x40 => F6, 0x41 => F7, 0x42 => F8,
+            0x43 => F9, 0x44 => F10, 0x57 => F11, 0x58 => F12,
+
+            0x45 => NumLock, 0x46 => ScrollLock,
+            0x47 => Numpad7, 0x48 => Numpad8, 0x49 => Numpad9, 0x4A => NumpadSubtract,
+            0x4B => Numpad4, 0x4C => Numpad5, 0x4D => Numpad6, 0x4E => NumpadAdd,
+            0x4F => Numpad1, 0x50 => Numpad2, 0x51 => Numpad3, 0x52 => Numpad0, 0x53 => NumpadDecimal,
+            0x56 => Oem102,
+
+            #[cfg(target_os = "windows")]
+            scancode => match scancode {
+                0xE01C => NumpadEnter, 0xE01D => RCtrl, 0xE035 => NumpadDivide, 0xE037 => PrintScreen,
+                0xE038 => RAlt, 0xE047 => Home, 0xE048 => Up, 0xE049 => PageUp, 0xE04B => Left, 0xE04D => Right,
+                0xE04F => End, 0xE050 => Down, 0xE051 => PageDown, 0xE052 => Insert, 0xE053 => Delete,
+                0xE05B => LLogo, 0xE05C => RLogo, 0xE05D => Menu,
+                _ => return None,
+            },
+
+            #[cfg(not(target_os = "windows"))]
+            scancode => match scancode {
+                0x60 => NumpadEnter, 0x61 => RCtrl, 0x62 => NumpadDivide, 0x63 => PrintScreen, 0x64 => RAlt,
+                0x66 => Home, 0x67 => Up, 0x68 => PageUp, 0x69 => Left, 0x6A => Right, 0x6B => End,
+                0x6C => Down, 0x6D => PageDown, 0x6E => Insert, 0x6F => Delete, 0x77 => Pause,
+                0x7D => LLogo, 0x7E => RLogo, 0x7F => Menu,
+                _ => return None,
+            },
+        })
+    }
 }
@@ -365,13 +365,21 @@ impl RenderGraph {
 
             for (node_index, node) in nodes2.iter_mut().enumerate() {
                 scope.spawn(async move {
+                    #[cfg(feature = "profiler")]
+                    let (label, _) = node.unzip_output();
+
                     let (output, sys, channel, done_sender) = node.unzip_input();
                     let input = RenderInput {
                         output,
                         parent_outputs: channel.receiver.recv().await.unwrap(),
                     };
 
-                    unsafe { sys.run_unsafe(input, world); }
+                    {
+                        #[cfg(feature = "profiler")]
+                        let _node_span = bevy_utils::tracing::info_span!("render_node", name = ?label).entered();
+                        unsafe { sys.run_unsafe(input, world); }
+                    }
+
                     done_sender.send(node_index).await.unwrap();
                 });
             }
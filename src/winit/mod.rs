@@ -1,6 +1,8 @@
 use crate::{
     asset::prelude::*,
     core::prelude::*,
+    graphics::prelude::*,
+    transform::prelude::*,
 };
 
 mod camera;
@@ -47,17 +49,20 @@ pub mod prelude {
         WindowConfig, WindowPosition, ClearColor,
         Renderer, SurfaceConfig, Frame,
         RenderStage, RenderLabel,
-        ColorExt as _, KeyCodeExt as _,
+        ColorExt as _, KeyCodeExt as _, MouseButtonExt as _,
     };
 }
 
 pub struct WinitSubsystem;
 impl Subsystem for WinitSubsystem {
     fn init(app: &mut App) {
+        let settings = app.settings().clone();
+        app.res_or(WindowConfig::default).apply_settings(&settings);
+
         WinitRunner::init(app)
             .set_runner(WinitRunner::run)
 
-            .stage(RenderStage::Begin, SystemStage::parallel()
+            .stage_before(ShutdownStage, RenderStage::Begin, SystemStage::parallel()
                 .with_system(Frame::prepare_sys.label(RenderLabel::PrepareFrame))
                 .with_system(Frame::init_sys
                     .label(RenderLabel::InitFrame)
@@ -69,18 +74,20 @@ impl Subsystem for WinitSubsystem {
                 .with_system(RenderGraph::begin_sys.at_end())
             )
 
-            .stage(RenderStage::Queue, SystemStage::parallel())
+            .stage_before(ShutdownStage, RenderStage::Queue, SystemStage::parallel())
 
-            .stage(RenderStage::Render, SystemStage::parallel()
+            .stage_before(ShutdownStage, RenderStage::Render, SystemStage::parallel()
                 .with_system(RenderGraph::render_sys.at_end())
             )
 
-            .stage(RenderStage::End, SystemStage::parallel()
+            .stage_before(ShutdownStage, RenderStage::End, SystemStage::parallel()
                 .with_system(Frame::present_sys.label(RenderLabel::PresentFrame))
                 .with_system(WindowConfig::visible_sys
                     .with_run_criteria(ShouldRun::once)
                     .after(RenderLabel::PresentFrame)
                 )
+                .with_system(WindowConfig::update_sys.after(RenderLabel::PresentFrame))
+                .with_system(WinitRunner::ime_sys)
             )
 
             .init_res::<Frame>()
@@ -95,6 +102,17 @@ impl Subsystem for WinitSubsystem {
             .asset::<Texture>()
             .asset::<Shader>()
             .asset_loader::<Texture>(TextureLoader)
-            .asset_loader::<Shader>(ShaderLoader);
+            .asset_loader::<Shader>(ShaderLoader)
+
+            .validate_requires::<Camera, GlobalTransform>();
+    }
+
+    fn deps() -> Vec<SubsystemId> {
+        vec![
+            SubsystemId::of::<CoreSubsystem>(),
+            SubsystemId::of::<AssetSubsystem>(),
+            SubsystemId::of::<GraphicsSubsystem>(),
+            SubsystemId::of::<TransformSubsystem>(),
+        ]
     }
 }
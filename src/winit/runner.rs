@@ -5,16 +5,19 @@ use crate::{
         WinitEventLoop, WinitWindow,
         WindowConfig, SurfaceConfig, ClearColor, Renderer,
         WindowResizedEvent, WindowMovedEvent, SuspendEvent, ResumeEvent,
-        KeyCodeExt as _,
+        KeyCodeExt as _, MouseButtonExt as _,
     },
 };
+use bevy_math::Vec2;
+use bevy_utils::HashSet;
 use futures_lite::future;
 use parking_lot::RwLock;
 use winit::{
+    dpi::PhysicalPosition,
     event::{
         StartCause,
-        Event, WindowEvent,
-        KeyboardInput, ElementState,
+        Event, WindowEvent, DeviceEvent,
+        KeyboardInput, ElementState, MouseScrollDelta, Ime as WinitIme,
     },
     event_loop::{
         EventLoop, ControlFlow,
@@ -73,11 +76,7 @@ impl WinitRunner {
             format: surface.get_supported_formats(&adapter)[0],
             width: size.width,
             height: size.height,
-            present_mode: if app.res::<WindowConfig>().unwrap().vsync {
-                wgpu::PresentMode::AutoVsync
-            } else {
-                wgpu::PresentMode::AutoNoVsync
-            },
+            present_mode: app.res::<WindowConfig>().unwrap().present_mode(),
             alpha_mode: wgpu::CompositeAlphaMode::Auto,
         };
 
@@ -87,6 +86,15 @@ impl WinitRunner {
             .insert_res(Renderer { device, queue, })
     }
 
+    /// Applies [`Ime`] to the window whenever it changes.
+    pub fn ime_sys(window: NonSend<WinitWindow>, ime: Option<Res<Ime>>) {
+        let Some(ime) = ime else { return };
+        if ime.is_changed() {
+            window.set_ime_allowed(ime.allowed);
+            window.set_ime_position(PhysicalPosition::new(ime.position.x, ime.position.y));
+        }
+    }
+
     pub fn run(mut app: App) -> ! {
         #[derive(Default)]
         struct State {
@@ -96,6 +104,9 @@ impl WinitRunner {
         let mut state = Some(State::default());
         app.exit_handle(Arc::clone(&state.as_ref().unwrap().exit));
 
+        // Scancodes of held keys, as winit reports repeats as regular presses.
+        let mut held_keys = HashSet::<u32>::default();
+
         let event_loop = app.remove_res_ns::<WinitEventLoop>().unwrap().0;
         event_loop.run(move |event, _, control_flow| {
             let (world, schedule) = app.unzip_mut();
@@ -118,13 +129,24 @@ impl WinitRunner {
                             },
                             WindowEvent::KeyboardInput {
                                 input: KeyboardInput {
-                                    state, virtual_keycode, ..
+                                    state, scancode, virtual_keycode, ..
                                 },
                                 ..
-                            } => if let Some(vkey) = virtual_keycode && let Some(key) = KeyCode::from_vkey(vkey) {
+                            } => {
+                                let pressed = state == ElementState::Pressed;
+                                let repeat = if pressed {
+                                    !held_keys.insert(scancode)
+                                } else {
+                                    held_keys.remove(&scancode);
+                                    false
+                                };
+
                                 world.send_event(KeyEvent {
-                                    pressed: state == ElementState::Pressed,
-                                    key,
+                                    pressed,
+                                    repeat,
+                                    key: virtual_keycode.and_then(KeyCode::from_vkey),
+                                    physical: KeyCode::from_scancode(scancode),
+                                    scancode,
                                 });
                             },
                             WindowEvent::ModifiersChanged(state) => world.send_event(KeyModifierEvent {
@@ -133,28 +155,51 @@ impl WinitRunner {
                                 logo: state.logo(),
                                 shift: state.shift(),
                             }),
+                            // Releases aren't reported while unfocused, so the next press can't be a repeat.
+                            WindowEvent::Focused(false) => held_keys.clear(),
+                            WindowEvent::ReceivedCharacter(c) => if !c.is_control() {
+                                world.send_event(TextInputEvent::Char(c));
+                            },
+                            WindowEvent::Ime(ime) => world.send_event(match ime {
+                                WinitIme::Enabled => TextInputEvent::ImeEnabled,
+                                WinitIme::Preedit(text, cursor) => TextInputEvent::Preedit { text, cursor },
+                                WinitIme::Commit(text) => TextInputEvent::Commit(text),
+                                WinitIme::Disabled => TextInputEvent::ImeDisabled,
+                            }),
+                            WindowEvent::MouseInput { state, button, .. } => world.send_event(MouseButtonEvent {
+                                pressed: state == ElementState::Pressed,
+                                button: MouseButton::from_winit(button),
+                            }),
+                            WindowEvent::MouseWheel { delta, .. } => world.send_event(match delta {
+                                MouseScrollDelta::LineDelta(x, y) => MouseWheelEvent {
+                                    delta: Vec2::new(x, y),
+                                    unit: MouseWheelUnit::Line,
+                                },
+                                MouseScrollDelta::PixelDelta(pos) => MouseWheelEvent {
+                                    delta: Vec2::new(pos.x as f32, pos.y as f32),
+                                    unit: MouseWheelUnit::Pixel,
+                                },
+                            }),
+                            WindowEvent::CursorMoved { position, .. } => world.send_event(CursorMovedEvent(
+                                Vec2::new(position.x as f32, position.y as f32),
+                            )),
+                            WindowEvent::CursorEntered { .. } => world.send_event(CursorEnteredEvent),
+                            WindowEvent::CursorLeft { .. } => world.send_event(CursorLeftEvent),
                             _ => {},
                         }
                     }
                 },
+                Event::DeviceEvent {
+                    event: DeviceEvent::MouseMotion { delta: (x, y) },
+                    ..
+                } => world.send_event(MouseMotionEvent(Vec2::new(x as f32, y as f32))),
                 Event::MainEventsCleared => {
                     schedule.run(world);
 
                     let exit = state.as_ref().unwrap().exit.read();
-                    let exit_code = match &*exit {
-                        Some(ExitReason::Graceful) => {
-                            log::info!("App exited gracefully");
-                            Some(0)
-                        },
-                        Some(ExitReason::Error(msg)) => {
-                            log::error!("App crashed: {}", msg);
-                            Some(1)
-                        },
-                        None => None,
-                    };
-
-                    if let Some(exit_code) = exit_code {
-                        *control_flow = ControlFlow::ExitWithCode(exit_code);
+                    if let Some(ref reason) = *exit {
+                        reason.log();
+                        *control_flow = ControlFlow::ExitWithCode(reason.exit_code());
                     }
                 },
                 Event::LoopDestroyed => {
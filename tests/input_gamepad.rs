@@ -0,0 +1,80 @@
+use avocado::{
+    prelude::*,
+    input::{
+        InputBinding, InputBindings,
+        GamepadButton, VirtualGamepads,
+    },
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+enum Action {
+    #[default]
+    Jump,
+}
+
+fn app() -> (App, VirtualGamepads) {
+    let pads = VirtualGamepads::default();
+    let mut app = App::new();
+    app
+        .insert_res(Args::new(vec![]))
+        .insert_res(CrashConfig {
+            enabled: false,
+            ..CrashConfig::default()
+        })
+        .gamepad_source(pads.clone())
+        .init::<CoreSubsystem>()
+        .init::<InputSubsystem<Action>>();
+
+    app.res_mut::<InputBindings<Action>>().unwrap().bind(Action::Jump, InputBinding::single([GamepadButton::South]));
+    app.world_mut().spawn(InputState::<Action>::default());
+
+    (app, pads)
+}
+
+fn update(app: &mut App) {
+    let (world, schedule) = app.unzip_mut();
+    schedule.run(world);
+}
+
+/// Whether the action is held and whether it was pressed this frame.
+fn jump(app: &mut App) -> (bool, bool) {
+    let world = app.world_mut();
+    let state = world.query::<&InputState<Action>>().single(world);
+    (state.pressed(&Action::Jump).unwrap_or(false), state.tapped(&Action::Jump).unwrap_or(false))
+}
+
+#[test]
+fn press_and_release_within_a_frame() {
+    let (mut app, pads) = app();
+    let pad = pads.connect("Virtual");
+    update(&mut app);
+
+    pad.press(GamepadButton::South);
+    pad.release(GamepadButton::South);
+    update(&mut app);
+    assert_eq!(jump(&mut app), (true, true));
+
+    update(&mut app);
+    assert_eq!(jump(&mut app), (false, false));
+}
+
+#[test]
+fn buttons_merge_over_every_gamepad() {
+    let (mut app, pads) = app();
+    let first = pads.connect("First");
+    let second = pads.connect("Second");
+    update(&mut app);
+
+    first.press(GamepadButton::South);
+    update(&mut app);
+    assert_eq!(jump(&mut app), (true, true));
+
+    second.press(GamepadButton::South);
+    first.release(GamepadButton::South);
+    update(&mut app);
+    assert_eq!(jump(&mut app), (true, false));
+
+    drop(second);
+    update(&mut app);
+    assert_eq!(jump(&mut app), (false, false));
+}